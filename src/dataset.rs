@@ -0,0 +1,305 @@
+//! Validation of datasets, i.e. batches of instances such as NDJSON exports.
+//!
+//! Besides validating every instance with an [`InstanceValidator`], it checks
+//! the dataset as a whole: identifiers are unique per identified type, and relationships
+//! point at instances of the dataset, or at references explicitly allowed.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::diagnostics::Diagnostic;
+use crate::error::ValidationError;
+use crate::instances::InstanceValidator;
+use crate::relationship::Reference;
+
+/// A diagnostic about the instance on a line of the dataset, counting from 1.
+#[derive(Debug)]
+pub struct DatasetDiagnostic {
+    pub line: usize,
+    pub diagnostic: Diagnostic,
+}
+
+impl fmt::Display for DatasetDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.diagnostic)
+    }
+}
+
+/// Validates datasets against the models registered with an [`InstanceValidator`].
+pub struct DatasetValidator<'a> {
+    validator: &'a InstanceValidator,
+    allowed_types: HashSet<String>,
+    allowed_references: HashSet<String>,
+}
+
+impl<'a> DatasetValidator<'a> {
+    pub fn new(validator: &'a InstanceValidator) -> Self {
+        Self {
+            validator,
+            allowed_types: HashSet::new(),
+            allowed_references: HashSet::new(),
+        }
+    }
+
+    /// Allows relationships to instances outside the dataset: a single one, e.g. `org.acme@1.0.0.Driver#D-1`,
+    /// or all the instances of a type and of its subtypes, e.g. `org.acme@1.0.0.Driver`.
+    pub fn allow(mut self, reference: &str) -> Self {
+        let reference = reference.strip_prefix("resource:").unwrap_or(reference);
+        if reference.contains('#') {
+            self.allowed_references.insert(reference.to_string());
+        } else {
+            self.allowed_types.insert(reference.to_string());
+        }
+        self
+    }
+
+    /// Validates a dataset with one JSON instance per line. Blank lines are skipped.
+    pub fn validate_ndjson(&self, ndjson: &str) -> Vec<DatasetDiagnostic> {
+        let mut diagnostics = vec![];
+        let mut instances = vec![];
+        for (index, line) in ndjson.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(instance) => instances.push((index + 1, instance)),
+                Err(error) => diagnostics.push(Self::diagnostic(index + 1, error.into())),
+            }
+        }
+        diagnostics.extend(self.validate_lines(&instances));
+        diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+        diagnostics
+    }
+
+    /// Validates a dataset of already parsed instances, the first one being on line 1.
+    pub fn validate_values(&self, instances: &[Value]) -> Vec<DatasetDiagnostic> {
+        let instances = instances.iter().cloned().enumerate().map(|(index, instance)| (index + 1, instance)).collect::<Vec<_>>();
+        self.validate_lines(&instances)
+    }
+
+    fn validate_lines(&self, instances: &[(usize, Value)]) -> Vec<DatasetDiagnostic> {
+        let model_manager = self.validator.model_manager();
+        let mut diagnostics = vec![];
+
+        // Invalid instances are reported on their own and left out of the dataset checks.
+        let mut valid = vec![];
+        for (line, instance) in instances {
+            match self.validator.validate_value(instance) {
+                Ok(()) => valid.push((*line, instance)),
+                Err(error) => diagnostics.push(Self::diagnostic(*line, error)),
+            }
+        }
+
+        // Identifiers are unique per identified type, e.g. a `TruckDriver` cannot reuse
+        // the identifier of a `Driver` it extends. The first line of each is kept,
+        // and the classes of the instances holding an identifier resolve relationships.
+        let mut first_lines: HashMap<(String, String), usize> = HashMap::new();
+        let mut identities: HashMap<String, HashSet<String>> = HashMap::new();
+        for (line, instance) in &valid {
+            let Ok(Some(identity)) = model_manager.get_identity(instance) else {
+                continue;
+            };
+            let key = (identity.identified_type, identity.identifier);
+            match first_lines.get(&key) {
+                Some(first_line) => diagnostics.push(Self::diagnostic(
+                    *line,
+                    ValidationError::DuplicateIdentifier {
+                        class_name: key.0,
+                        identifier: key.1,
+                        first_line: *first_line,
+                    },
+                )),
+                None => {
+                    identities.entry(key.1.clone()).or_default().insert(identity.class_name);
+                    first_lines.insert(key, *line);
+                }
+            }
+        }
+
+        for (line, instance) in &valid {
+            let references = match model_manager.get_references(instance) {
+                Ok(references) => references,
+                Err(error) => {
+                    diagnostics.push(Self::diagnostic(*line, error));
+                    continue;
+                }
+            };
+            for reference in references {
+                let present = identities.get(&reference.identifier).is_some_and(|classes| {
+                    classes.iter().any(|class_name| model_manager.is_assignable(class_name, &reference.type_name))
+                });
+                if !present && !self.is_allowed(&reference) {
+                    diagnostics.push(Self::diagnostic(
+                        *line,
+                        ValidationError::DanglingReference {
+                            property: reference.property,
+                            reference: format!("{}#{}", reference.type_name, reference.identifier),
+                        },
+                    ));
+                }
+            }
+        }
+
+        diagnostics.sort_by_key(|diagnostic| diagnostic.line);
+        diagnostics
+    }
+
+    fn is_allowed(&self, reference: &Reference) -> bool {
+        let model_manager = self.validator.model_manager();
+        self.allowed_references.contains(&format!("{}#{}", reference.type_name, reference.identifier))
+            || self.allowed_types.iter().any(|type_name| model_manager.is_assignable(&reference.type_name, type_name))
+    }
+
+    fn diagnostic(line: usize, error: ValidationError) -> DatasetDiagnostic {
+        DatasetDiagnostic {
+            line,
+            diagnostic: Diagnostic::error(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn validator() -> InstanceValidator {
+        let string = |name: &str| json!({ "$class": "concerto.metamodel@1.0.0.StringProperty", "name": name, "isArray": false, "isOptional": false });
+        let relationship = |name: &str, is_array: bool| json!({
+            "$class": "concerto.metamodel@1.0.0.RelationshipProperty",
+            "name": name,
+            "type": { "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": "Driver" },
+            "isArray": is_array,
+            "isOptional": true
+        });
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.acme@1.0.0",
+            "declarations": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration",
+                    "name": "Driver",
+                    "isAbstract": false,
+                    "identified": { "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": "id" },
+                    "properties": [string("id")]
+                },
+                {
+                    "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration",
+                    "name": "TruckDriver",
+                    "isAbstract": false,
+                    "superType": { "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": "Driver" },
+                    "properties": []
+                },
+                {
+                    "$class": "concerto.metamodel@1.0.0.AssetDeclaration",
+                    "name": "Car",
+                    "isAbstract": false,
+                    "identified": { "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": "id" },
+                    "properties": [string("id"), relationship("driver", false), relationship("passengers", true)]
+                }
+            ]
+        });
+        let mut validator = InstanceValidator::new().unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+        validator
+    }
+
+    fn errors(diagnostics: &[DatasetDiagnostic]) -> Vec<(usize, &ValidationError)> {
+        diagnostics.iter().map(|diagnostic| (diagnostic.line, &diagnostic.diagnostic.error)).collect()
+    }
+
+    #[test]
+    fn test_valid_dataset() {
+        let validator = validator();
+        let ndjson = r#"
+{ "$class": "org.acme@1.0.0.Driver", "id": "D-1" }
+{ "$class": "org.acme@1.0.0.TruckDriver", "id": "D-2" }
+{ "$class": "org.acme@1.0.0.Car", "id": "D-1", "driver": "resource:org.acme@1.0.0.Driver#D-2", "passengers": ["D-1"] }
+"#;
+        let diagnostics = DatasetValidator::new(&validator).validate_ndjson(ndjson);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_duplicate_identifiers() {
+        let validator = validator();
+        let diagnostics = DatasetValidator::new(&validator).validate_values(&[
+            json!({ "$class": "org.acme@1.0.0.Driver", "id": "D-1" }),
+            json!({ "$class": "org.acme@1.0.0.TruckDriver", "id": "D-1" }),
+            json!({ "$class": "org.acme@1.0.0.Driver", "id": "D-1" }),
+        ]);
+        assert!(
+            matches!(
+                errors(&diagnostics)[..],
+                [
+                    (2, ValidationError::DuplicateIdentifier { ref class_name, first_line: 1, .. }),
+                    (3, ValidationError::DuplicateIdentifier { first_line: 1, .. })
+                ] if class_name == "org.acme@1.0.0.Driver"
+            ),
+            "{:?}",
+            diagnostics
+        );
+
+        // Identifiers of unrelated identified types do not clash.
+        let diagnostics = DatasetValidator::new(&validator).validate_values(&[
+            json!({ "$class": "org.acme@1.0.0.Driver", "id": "X-1" }),
+            json!({ "$class": "org.acme@1.0.0.Car", "id": "X-1" }),
+        ]);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_dangling_references() {
+        let validator = validator();
+        let ndjson = r#"{ "$class": "org.acme@1.0.0.Driver", "id": "D-1" }
+{ "$class": "org.acme@1.0.0.Car", "id": "C-1", "driver": "resource:org.acme@1.0.0.TruckDriver#D-1" }
+{ "$class": "org.acme@1.0.0.Car", "id": "C-2", "driver": "D-2", "passengers": ["D-1", "D-3"] }
+"#;
+        let diagnostics = DatasetValidator::new(&validator).validate_ndjson(ndjson);
+        let references = diagnostics
+            .iter()
+            .map(|diagnostic| match &diagnostic.diagnostic.error {
+                ValidationError::DanglingReference { property, reference } => (diagnostic.line, property.as_str(), reference.as_str()),
+                error => panic!("unexpected error {:?}", error),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            references,
+            [
+                (2, "driver", "org.acme@1.0.0.TruckDriver#D-1"),
+                (3, "driver", "org.acme@1.0.0.Driver#D-2"),
+                (3, "passengers", "org.acme@1.0.0.Driver#D-3"),
+            ]
+        );
+
+        let allowed = DatasetValidator::new(&validator)
+            .allow("org.acme@1.0.0.TruckDriver")
+            .allow("resource:org.acme@1.0.0.Driver#D-2")
+            .allow("org.acme@1.0.0.Driver#D-3");
+        let diagnostics = allowed.validate_ndjson(ndjson);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_invalid_instances() {
+        let validator = validator();
+        let ndjson = "{ \"$class\": \"org.acme@1.0.0.Driver\" }\n\nnot json\n{ \"$class\": \"org.acme@1.0.0.Car\", \"id\": \"C-1\", \"driver\": \"D-1\" }";
+        let diagnostics = DatasetValidator::new(&validator).validate_ndjson(ndjson);
+        // The invalid driver is left out, so the reference to it dangles.
+        assert!(
+            matches!(
+                errors(&diagnostics)[..],
+                [
+                    (1, ValidationError::MissingRequiredProperty { .. }),
+                    (3, ValidationError::JsonError(_)),
+                    (4, ValidationError::DanglingReference { .. })
+                ]
+            ),
+            "{:?}",
+            diagnostics
+        );
+        assert_eq!(diagnostics[2].to_string(), "line 4: error[CV0053]: Dangling reference org.acme@1.0.0.Driver#D-1 in driver");
+    }
+}
@@ -0,0 +1,238 @@
+//! Minimal ISO-8601 support for Concerto `DateTime` values.
+//! Only the extended calendar format is understood, e.g.
+//! `2024-02-29`, `2024-02-29T13:45:00Z` or `2024-02-29T13:45:00.123+02:00`,
+//! which covers what the JS implementation produces when serializing.
+
+use crate::options::InstanceOptions;
+
+/// A parsed ISO-8601 date or date-time.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DateTime {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    /// `None` for date-only values.
+    pub time: Option<Time>,
+    /// Offset from UTC in minutes, `None` when no offset is given.
+    pub offset_minutes: Option<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Time {
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+}
+
+impl DateTime {
+    /// Parses an ISO-8601 date or date-time, checking that the date exists in the calendar.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (date, rest) = match value.find(['T', 't']) {
+            Some(index) => (&value[..index], Some(&value[index + 1..])),
+            None => (value, None),
+        };
+
+        let (year, month, day) = parse_date(date).ok_or_else(|| format!("'{}' is not a YYYY-MM-DD date", date))?;
+        if month == 0 || month > 12 {
+            return Err(format!("month {} does not exist", month));
+        }
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(format!("day {} does not exist in {:04}-{:02}", day, year, month));
+        }
+
+        let (time, offset_minutes) = match rest {
+            Some(rest) => {
+                let (time, offset) = split_offset(rest);
+                let time = parse_time(time).ok_or_else(|| format!("'{}' is not a HH:MM:SS time", time))?;
+                let offset = offset.map(parse_offset).transpose()?;
+                (Some(time), offset)
+            }
+            None => (None, None),
+        };
+
+        Ok(DateTime { year, month, day, time, offset_minutes })
+    }
+
+    /// Checks the value has the form `options` accept: a time with a UTC offset,
+    /// or only a date with [`InstanceOptions::allow_date_only`].
+    pub fn check(&self, options: &InstanceOptions) -> Result<(), String> {
+        match (&self.time, self.offset_minutes) {
+            (None, _) if !options.allow_date_only => Err("expected a date and time".to_string()),
+            (Some(_), None) => Err("expected a UTC offset, e.g. Z or +02:00".to_string()),
+            (Some(_), Some(offset)) if offset != 0 && options.require_utc => Err("expected a UTC date and time".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Formats the value in UTC like `Date.toISOString` in JS, e.g. `2024-02-29T11:15:10.500Z`.
+    /// Dates are taken at midnight UTC and sub-millisecond digits are dropped.
+    pub fn to_utc_string(&self) -> String {
+        let time = self.time.clone().unwrap_or(Time { hour: 0, minute: 0, second: 0, nanosecond: 0 });
+        let minutes = days_from_civil(self.year, self.month, self.day) * 24 * 60
+            + (time.hour * 60 + time.minute) as i64
+            - self.offset_minutes.unwrap_or(0) as i64;
+        let (year, month, day) = civil_from_days(minutes.div_euclid(24 * 60));
+        let minute_of_day = minutes.rem_euclid(24 * 60);
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+            year,
+            month,
+            day,
+            minute_of_day / 60,
+            minute_of_day % 60,
+            time.second,
+            time.nanosecond / 1_000_000
+        )
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year as i64 - 1 } else { year as i64 };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month as i64 + 9) % 12) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The proleptic Gregorian date of a number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn parse_date(date: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = date.split('-');
+    let year = parse_digits(parts.next()?, 4)?;
+    let month = parse_digits(parts.next()?, 2)?;
+    let day = parse_digits(parts.next()?, 2)?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((year as i32, month, day))
+}
+
+fn split_offset(value: &str) -> (&str, Option<&str>) {
+    match value.find(['Z', 'z', '+', '-']) {
+        Some(index) => (&value[..index], Some(&value[index..])),
+        None => (value, None),
+    }
+}
+
+fn parse_time(time: &str) -> Option<Time> {
+    let (time, fraction) = match time.split_once('.') {
+        Some((time, fraction)) => (time, Some(fraction)),
+        None => (time, None),
+    };
+    let mut parts = time.split(':');
+    let hour = parse_digits(parts.next()?, 2)?;
+    let minute = parse_digits(parts.next()?, 2)?;
+    let second = match parts.next() {
+        Some(second) => parse_digits(second, 2)?,
+        None if fraction.is_none() => 0,
+        None => return None,
+    };
+    if parts.next().is_some() || hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let nanosecond = match fraction {
+        Some(fraction) if !fraction.is_empty() && fraction.len() <= 9 && fraction.chars().all(|c| c.is_ascii_digit()) => {
+            format!("{:0<9}", fraction).parse().ok()?
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+    Some(Time { hour, minute, second, nanosecond })
+}
+
+fn parse_offset(offset: &str) -> Result<i32, String> {
+    if offset.eq_ignore_ascii_case("z") {
+        return Ok(0);
+    }
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits = offset[1..].replace(':', "");
+    let (hours, minutes) = match digits.len() {
+        2 => (parse_digits(&digits, 2), Some(0)),
+        4 => (parse_digits(&digits[..2], 2), parse_digits(&digits[2..], 2)),
+        _ => (None, None),
+    };
+    match (hours, minutes) {
+        (Some(hours), Some(minutes)) if hours <= 23 && minutes <= 59 => Ok(sign * (hours * 60 + minutes) as i32),
+        _ => Err(format!("'{}' is not a valid UTC offset", offset)),
+    }
+}
+
+fn parse_digits(value: &str, length: usize) -> Option<u32> {
+    if value.len() != length || !value.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date_time() {
+        let value = DateTime::parse("2024-02-29T13:45:10.5+02:30").unwrap();
+        assert_eq!((value.year, value.month, value.day), (2024, 2, 29));
+        assert_eq!(value.time, Some(Time { hour: 13, minute: 45, second: 10, nanosecond: 500_000_000 }));
+        assert_eq!(value.offset_minutes, Some(150));
+
+        let value = DateTime::parse("2024-01-01T00:00:00Z").unwrap();
+        assert_eq!(value.offset_minutes, Some(0));
+
+        let value = DateTime::parse("2024-01-01").unwrap();
+        assert_eq!(value.time, None);
+        assert_eq!(value.offset_minutes, None);
+    }
+
+    #[test]
+    fn test_calendar_dates() {
+        assert!(DateTime::parse("2000-02-29").is_ok());
+        assert!(DateTime::parse("1900-02-29").is_err());
+        assert!(DateTime::parse("2023-02-29").is_err());
+        assert!(DateTime::parse("2023-04-31").is_err());
+        assert!(DateTime::parse("2023-13-01").is_err());
+    }
+
+    #[test]
+    fn test_to_utc_string() {
+        for (value, utc) in [
+            ("2024-02-29T13:45:10.5+02:30", "2024-02-29T11:15:10.500Z"),
+            ("2024-02-29T23:30:00-01:00", "2024-03-01T00:30:00.000Z"),
+            ("2024-01-01T00:15:00+01:00", "2023-12-31T23:15:00.000Z"),
+            ("1969-12-31T23:59:59.123456Z", "1969-12-31T23:59:59.123Z"),
+            ("2024-02-29", "2024-02-29T00:00:00.000Z"),
+        ] {
+            assert_eq!(DateTime::parse(value).unwrap().to_utc_string(), utc, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_malformed_values() {
+        for value in ["", "2024", "2024-1-01", "24-01-01", "2024-01-01T", "2024-01-01T25:00:00Z", "2024-01-01T10:00:00+2", "2024-01-01T10:00:00.Z", "yesterday"] {
+            assert!(DateTime::parse(value).is_err(), "{} should be rejected", value);
+        }
+    }
+}
@@ -0,0 +1,378 @@
+//! Decorator Command Sets (DCS).
+//!
+//! A DCS is a JSON document listing commands that add, upsert or remove
+//! decorators on the declarations, properties and map keys and values of a model,
+//! selected by namespace, declaration name, property name(s) or type.
+//! The document is validated against the embedded
+//! `org.accordproject.decoratorcommands@0.3.0` model with the same
+//! [`ModelManager`](crate::model_manager::ModelManager) used for the metamodel.
+//! The `REMOVE` command type is an extension of this crate. Decorator namespaces
+//! and included command sets are not supported, and are rejected.
+
+use std::sync::OnceLock;
+
+use serde_json::{json, Value};
+
+use crate::error::{ValidationError, ValidationResult};
+use crate::model_manager::{JsonObject, ModelManager};
+use crate::semantic::model_file::{short_class_name, str_field};
+
+const DECORATOR_COMMAND_SET_CLASS: &str = "org.accordproject.decoratorcommands@0.3.0.DecoratorCommandSet";
+
+// Reference to hold singleton instance of the DCS aware ModelManager
+static DCS_MODEL_MANAGER: OnceLock<Option<ModelManager>> = OnceLock::new();
+
+/// A validated Decorator Command Set.
+#[derive(Debug, Clone)]
+pub struct DecoratorCommandSet {
+    document: Value,
+}
+
+impl DecoratorCommandSet {
+    /// Parses a DCS JSON document and validates it against the DCS model.
+    pub fn from_json(json: &str) -> ValidationResult<Self> {
+        let document: Value = serde_json::from_str(json)?;
+
+        let class_name = document.get("$class").and_then(Value::as_str).unwrap_or_default();
+        if class_name != DECORATOR_COMMAND_SET_CLASS {
+            return Err(ValidationError::TypeMismatch {
+                expected: DECORATOR_COMMAND_SET_CLASS.to_string(),
+                found: class_name.to_string(),
+            });
+        }
+
+        let model_manager = DCS_MODEL_MANAGER
+            .get_or_init(|| {
+                let mut model_manager = ModelManager::new().ok()?;
+                let dcs_model = serde_json::from_str(include_str!("../decoratorcommands.json")).ok()?;
+                model_manager.add_model(&dcs_model).ok()?;
+                Some(model_manager)
+            })
+            .as_ref()
+            .ok_or(ValidationError::ValidatorInitializationError)?;
+        model_manager.validate_instance(&document)?;
+        check_supported(&document)?;
+
+        Ok(Self { document })
+    }
+
+    pub fn name(&self) -> &str {
+        self.document.get("name").and_then(Value::as_str).unwrap_or_default()
+    }
+
+    pub fn version(&self) -> &str {
+        self.document.get("version").and_then(Value::as_str).unwrap_or_default()
+    }
+
+    /// Applies the commands, in order, to a `Model` or `Models` AST
+    /// and returns the decorated copy.
+    pub fn apply(&self, ast: &Value) -> ValidationResult<Value> {
+        let mut decorated = ast.clone();
+        let models = match decorated.as_object_mut() {
+            Some(root) if short_class_name(root) == "Model" => vec![root],
+            Some(root) if short_class_name(root) == "Models" => root
+                .get_mut("models")
+                .and_then(Value::as_array_mut)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_object_mut)
+                .collect(),
+            _ => {
+                return Err(ValidationError::UnexpectedType {
+                    expected: "Model or Models".to_string(),
+                })
+            }
+        };
+
+        let commands = self.document.get("commands").and_then(Value::as_array).into_iter().flatten();
+        let commands = commands.filter_map(Value::as_object).collect::<Vec<_>>();
+        for model in models {
+            for command in &commands {
+                execute_command(model, command);
+            }
+        }
+
+        Ok(decorated)
+    }
+}
+
+fn check_supported(document: &Value) -> ValidationResult<()> {
+    let unsupported = |message: &str| ValidationError::ValidationFailed { message: message.to_string() };
+    if document.get("includes").and_then(Value::as_array).is_some_and(|includes| !includes.is_empty()) {
+        return Err(unsupported("including other decorator command sets is not supported"));
+    }
+    let mut commands = document.get("commands").and_then(Value::as_array).into_iter().flatten();
+    if commands.any(|command| command.get("decoratorNamespace").is_some()) {
+        return Err(unsupported("decoratorNamespace is not supported, decorators are applied as they are"));
+    }
+    Ok(())
+}
+
+fn execute_command(model: &mut JsonObject, command: &JsonObject) {
+    let empty = JsonObject::new();
+    let target = command.get("target").and_then(Value::as_object).unwrap_or(&empty);
+    let Some(decorator) = command.get("decorator") else {
+        return;
+    };
+    let command_type = str_field(command, "type").unwrap_or_default();
+
+    let namespace = str_field(model, "namespace").unwrap_or_default();
+    let namespace_name = namespace.split_once('@').map_or(namespace, |(name, _)| name);
+    if !matches(str_field(target, "namespace"), &[namespace, namespace_name]) {
+        return;
+    }
+
+    let map_element = str_field(target, "mapElement");
+    let targets_properties = ["property", "properties", "type"].iter().any(|key| target.contains_key(*key));
+    let declarations = model.get_mut("declarations").and_then(Value::as_array_mut).into_iter().flatten();
+
+    for declaration in declarations.filter_map(Value::as_object_mut) {
+        let name = str_field(declaration, "name").unwrap_or_default();
+        if !matches(str_field(target, "declaration"), &[name]) {
+            continue;
+        }
+        if let Some(map_element) = map_element {
+            apply_to_map_elements(declaration, map_element, target, command_type, decorator);
+            continue;
+        }
+        if !targets_properties {
+            apply_decorator(declaration, command_type, decorator);
+            continue;
+        }
+        let properties = declaration.get_mut("properties").and_then(Value::as_array_mut).into_iter().flatten();
+        for property in properties.filter_map(Value::as_object_mut) {
+            if matches_property(target, property) {
+                apply_decorator(property, command_type, decorator);
+            }
+        }
+    }
+}
+
+/// Map targets select the key, the value or both of map declarations,
+/// possibly of a given type, e.g. `String` for a `StringMapKeyType`.
+fn apply_to_map_elements(declaration: &mut JsonObject, map_element: &str, target: &JsonObject, command_type: &str, decorator: &Value) {
+    if short_class_name(declaration) != "MapDeclaration" {
+        return;
+    }
+    let parts: &[&str] = match map_element {
+        "KEY" => &["key"],
+        "VALUE" => &["value"],
+        _ => &["key", "value"],
+    };
+    for part in parts {
+        let Some(node) = declaration.get_mut(*part).and_then(Value::as_object_mut) else {
+            continue;
+        };
+        if matches(str_field(target, "type"), &[node_type(node)]) {
+            apply_decorator(node, command_type, decorator);
+        }
+    }
+}
+
+/// An absent target field matches everything.
+fn matches(target: Option<&str>, candidates: &[&str]) -> bool {
+    target.is_none_or(|target| candidates.contains(&target))
+}
+
+fn matches_property(target: &JsonObject, property: &JsonObject) -> bool {
+    let name = str_field(property, "name").unwrap_or_default();
+    let in_properties = match target.get("properties").and_then(Value::as_array) {
+        Some(properties) => properties.iter().any(|p| p.as_str() == Some(name)),
+        None => true,
+    };
+    in_properties && matches(str_field(target, "property"), &[name]) && matches(str_field(target, "type"), &[node_type(property)])
+}
+
+/// The type name of a property or map element, e.g. `String` for a `StringProperty`
+/// or a `StringMapKeyType`, or the referenced type for object and relationship types.
+fn node_type(node: &JsonObject) -> &str {
+    match node.get("type").and_then(Value::as_object) {
+        Some(type_identifier) => str_field(type_identifier, "name").unwrap_or_default(),
+        None => {
+            let class = short_class_name(node);
+            ["Property", "MapKeyType", "MapValueType"]
+                .iter()
+                .find_map(|suffix| class.strip_suffix(suffix))
+                .unwrap_or_default()
+        }
+    }
+}
+
+fn apply_decorator(node: &mut JsonObject, command_type: &str, decorator: &Value) {
+    let name = decorator.get("name");
+    let decorators = node.entry("decorators").or_insert_with(|| json!([]));
+    let Some(decorators) = decorators.as_array_mut() else {
+        return;
+    };
+
+    match command_type {
+        "APPEND" => decorators.push(decorator.clone()),
+        "UPSERT" => match decorators.iter_mut().find(|existing| existing.get("name") == name) {
+            Some(existing) => *existing = decorator.clone(),
+            None => decorators.push(decorator.clone()),
+        },
+        "REMOVE" => decorators.retain(|existing| existing.get("name") != name),
+        _ => {}
+    }
+
+    if decorators.is_empty() {
+        node.remove("decorators");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model() -> Value {
+        json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                    "name": "Person",
+                    "isAbstract": false,
+                    "decorators": [
+                        { "$class": "concerto.metamodel@1.0.0.Decorator", "name": "Deprecated" }
+                    ],
+                    "properties": [
+                        { "$class": "concerto.metamodel@1.0.0.StringProperty", "name": "firstName", "isArray": false, "isOptional": false },
+                        { "$class": "concerto.metamodel@1.0.0.IntegerProperty", "name": "age", "isArray": false, "isOptional": true }
+                    ]
+                },
+                {
+                    "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                    "name": "Address",
+                    "isAbstract": false,
+                    "properties": []
+                },
+                {
+                    "$class": "concerto.metamodel@1.0.0.MapDeclaration",
+                    "name": "Phones",
+                    "key": { "$class": "concerto.metamodel@1.0.0.StringMapKeyType" },
+                    "value": {
+                        "$class": "concerto.metamodel@1.0.0.ObjectMapValueType",
+                        "type": { "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": "Address" }
+                    }
+                }
+            ]
+        })
+    }
+
+    fn command_set(commands: Value) -> String {
+        json!({
+            "$class": DECORATOR_COMMAND_SET_CLASS,
+            "name": "test",
+            "version": "1.0.0",
+            "commands": commands
+        })
+        .to_string()
+    }
+
+    fn command(target: Value, command_type: &str, decorator: &str) -> Value {
+        let mut target = target;
+        target["$class"] = json!("org.accordproject.decoratorcommands@0.3.0.CommandTarget");
+        json!({
+            "$class": "org.accordproject.decoratorcommands@0.3.0.Command",
+            "type": command_type,
+            "target": target,
+            "decorator": { "$class": "concerto.metamodel@1.0.0.Decorator", "name": decorator }
+        })
+    }
+
+    fn decorator_names(node: &Value) -> Vec<&str> {
+        node.get("decorators")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|d| d.get("name").and_then(Value::as_str))
+            .collect()
+    }
+
+    #[test]
+    fn test_invalid_command_sets() {
+        assert!(DecoratorCommandSet::from_json("{}").is_err());
+        let unknown_type = command_set(json!([command(json!({}), "DELETE", "PII")]));
+        assert!(DecoratorCommandSet::from_json(&unknown_type).is_err());
+        let missing_decorator = command_set(json!([{
+            "$class": "org.accordproject.decoratorcommands@0.3.0.Command",
+            "type": "UPSERT",
+            "target": { "$class": "org.accordproject.decoratorcommands@0.3.0.CommandTarget" }
+        }]));
+        assert!(DecoratorCommandSet::from_json(&missing_decorator).is_err());
+        let unknown_map_element = command_set(json!([command(json!({ "mapElement": "ENTRY" }), "APPEND", "PII")]));
+        assert!(DecoratorCommandSet::from_json(&unknown_map_element).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_features() {
+        let mut namespaced = command(json!({}), "APPEND", "PII");
+        namespaced["decoratorNamespace"] = json!("org.decorators@1.0.0");
+        assert!(matches!(DecoratorCommandSet::from_json(&command_set(json!([namespaced]))), Err(ValidationError::ValidationFailed { .. })));
+
+        let mut including: Value = serde_json::from_str(&command_set(json!([]))).unwrap();
+        including["includes"] = json!([{
+            "$class": "org.accordproject.decoratorcommands@0.3.0.DecoratorCommandSetReference",
+            "name": "base",
+            "version": "1.0.0"
+        }]);
+        assert!(matches!(DecoratorCommandSet::from_json(&including.to_string()), Err(ValidationError::ValidationFailed { .. })));
+    }
+
+    #[test]
+    fn test_declaration_and_namespace_targets() {
+        let dcs = DecoratorCommandSet::from_json(&command_set(json!([
+            command(json!({ "namespace": "org.example", "declaration": "Person" }), "UPSERT", "Deprecated"),
+            command(json!({ "namespace": "org.example@1.0.0" }), "APPEND", "Entity"),
+            command(json!({ "namespace": "org.other" }), "APPEND", "Ignored")
+        ])))
+        .unwrap();
+        assert_eq!((dcs.name(), dcs.version()), ("test", "1.0.0"));
+
+        let decorated = dcs.apply(&model()).unwrap();
+        assert_eq!(decorator_names(&decorated["declarations"][0]), ["Deprecated", "Entity"]);
+        assert_eq!(decorator_names(&decorated["declarations"][1]), ["Entity"]);
+    }
+
+    #[test]
+    fn test_property_targets() {
+        let dcs = DecoratorCommandSet::from_json(&command_set(json!([
+            command(json!({ "declaration": "Person", "property": "firstName" }), "APPEND", "PII"),
+            command(json!({ "type": "Integer" }), "APPEND", "Range"),
+            command(json!({ "properties": ["firstName", "age"] }), "UPSERT", "Indexed"),
+            command(json!({ "declaration": "Person" }), "REMOVE", "Deprecated")
+        ])))
+        .unwrap();
+
+        let decorated = dcs.apply(&model()).unwrap();
+        let person = &decorated["declarations"][0];
+        assert_eq!(decorator_names(&person["properties"][0]), ["PII", "Indexed"]);
+        assert_eq!(decorator_names(&person["properties"][1]), ["Range", "Indexed"]);
+        assert!(person.get("decorators").is_none());
+    }
+
+    #[test]
+    fn test_map_targets() {
+        let dcs = DecoratorCommandSet::from_json(&command_set(json!([
+            command(json!({ "declaration": "Phones", "mapElement": "KEY" }), "APPEND", "Key"),
+            command(json!({ "mapElement": "KEY_VALUE" }), "APPEND", "Entry"),
+            command(json!({ "mapElement": "VALUE", "type": "Address" }), "APPEND", "Located"),
+            command(json!({ "mapElement": "VALUE", "type": "String" }), "APPEND", "Ignored")
+        ])))
+        .unwrap();
+
+        let decorated = dcs.apply(&model()).unwrap();
+        let phones = &decorated["declarations"][2];
+        assert_eq!(decorator_names(&phones["key"]), ["Key", "Entry"]);
+        assert_eq!(decorator_names(&phones["value"]), ["Entry", "Located"]);
+        assert!(phones.get("decorators").is_none());
+        assert_eq!(decorator_names(&decorated["declarations"][0]), ["Deprecated"]);
+    }
+
+    #[test]
+    fn test_apply_requires_a_model() {
+        let dcs = DecoratorCommandSet::from_json(&command_set(json!([]))).unwrap();
+        assert!(dcs.apply(&json!({ "$class": "concerto.metamodel@1.0.0.Decorator" })).is_err());
+    }
+}
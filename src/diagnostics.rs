@@ -0,0 +1,64 @@
+//! Diagnostics reported by [`Validator::diagnose`](crate::Validator::diagnose).
+//!
+//! By default every violation of the metamodel or of the semantic rules is an error.
+//! Lint rules report warnings and informational diagnostics, which point
+//! at questionable models without making them invalid.
+//! The severity of each [`Rule`] can be changed with a [`RuleSet`](crate::rules::RuleSet).
+
+use std::fmt;
+
+use crate::error::ValidationError;
+use crate::rules::Rule;
+
+/// How serious a [`Diagnostic`] is, from the least to the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A finding about a model, e.g.
+/// `warning[CV0038]: Unused import Person from org.example@1.0.0 [unused-import]`.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// The rule that reported the finding, `None` for metamodel errors.
+    pub rule: Option<Rule>,
+    pub error: ValidationError,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, error: ValidationError) -> Self {
+        let rule = Rule::of(&error);
+        Self { severity, rule, error }
+    }
+
+    pub fn error(error: ValidationError) -> Self {
+        Self::new(Severity::Error, error)
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.error.code(), self.error)?;
+        match self.rule {
+            Some(rule) => write!(f, " [{}]", rule),
+            None => Ok(()),
+        }
+    }
+}
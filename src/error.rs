@@ -51,4 +51,259 @@ pub enum ValidationError {
 
     #[error("Validator Initialisation Error")]
     ValidatorInitializationError,
+
+    #[error("Invalid identifier '{name}' used as {context}")]
+    InvalidIdentifier { name: String, context: String },
+
+    #[error("Reserved identifier '{name}' cannot be used as {context}")]
+    ReservedIdentifier { name: String, context: String },
+
+    #[error("Invalid namespace '{namespace}': {reason}")]
+    InvalidNamespace { namespace: String, reason: String },
+
+    #[error("Invalid version in namespace '{namespace}': {reason}")]
+    InvalidNamespaceVersion { namespace: String, reason: String },
+
+    #[error("Namespace '{namespace}' must be versioned")]
+    UnversionedNamespace { namespace: String },
+
+    #[error("Invalid validator on {context}: lower bound {lower} is greater than upper bound {upper}")]
+    InvalidValidatorRange { context: String, lower: String, upper: String },
+
+    #[error("Invalid validator on {context}: {bound} {reason}")]
+    InvalidValidatorBound { context: String, bound: String, reason: String },
+
+    #[error("Invalid regex pattern on {context}: /{pattern}/ {reason}")]
+    InvalidRegexPattern { context: String, pattern: String, reason: String },
+
+    #[error("Invalid default value '{value}' on {context}: {reason}")]
+    InvalidDefaultValue { context: String, value: String, reason: String },
+
+    #[error("Invalid enum declaration {name}: {reason}")]
+    InvalidEnumDeclaration { name: String, reason: String },
+
+    #[error("Duplicate value '{value}' in enum {name}")]
+    DuplicateEnumValue { name: String, value: String },
+
+    #[error("Invalid relationship target {target} on {context}: {reason}")]
+    InvalidRelationshipTarget { context: String, target: String, reason: String },
+
+    #[error("Relationship on {context} targets {target}, which is not an identified type")]
+    UnidentifiedRelationshipTarget { context: String, target: String },
+
+    #[error("Undeclared type {name} used on {context}")]
+    UndeclaredType { context: String, name: String },
+
+    #[error("{kind} {declaration} cannot extend {super_kind} {super_type}")]
+    IncompatibleSupertype { declaration: String, kind: String, super_type: String, super_kind: String },
+
+    #[error("{declaration} cannot extend scalar {scalar}")]
+    ScalarSupertype { declaration: String, scalar: String },
+
+    #[error("Unknown decorator @{name} on {context}")]
+    UnknownDecorator { name: String, context: String },
+
+    #[error("Invalid arguments for decorator @{name} on {context}: {reason}")]
+    InvalidDecoratorArguments { name: String, context: String, reason: String },
+
+    #[error("Invalid map declaration {name}: {reason}")]
+    InvalidMapDeclaration { name: String, reason: String },
+
+    #[error("Invalid identifying field '{field}' in {declaration}: {reason}")]
+    InvalidIdentifyingField { declaration: String, field: String, reason: String },
+
+    #[error("{declaration} cannot redeclare the identity inherited from {ancestor}")]
+    IdentityRedeclared { declaration: String, ancestor: String },
+
+    #[error("Unused import {name} from {namespace}")]
+    UnusedImport { namespace: String, name: String },
+
+    #[error("Declaration {name} is never referenced")]
+    UnreferencedDeclaration { name: String },
+
+    #[error("Declaration name {name} should be PascalCase")]
+    NonPascalCaseDeclaration { name: String },
+
+    #[error("Property name {context} should be camelCase")]
+    NonCamelCaseProperty { context: String },
+
+    #[error("Declaration {name} has no properties")]
+    EmptyConcept { name: String },
+
+    #[error("Property {context} has no @{decorator} decorator")]
+    MissingDescription { context: String, decorator: String },
+
+    #[error("Invalid rule configuration: {reason}")]
+    InvalidRuleConfiguration { reason: String },
+
+    #[error("Cannot create an instance of abstract type {class_name}")]
+    AbstractInstance { class_name: String },
+
+    #[error("Invalid DateTime '{value}' for {property}: {reason}")]
+    InvalidDateTime { property: String, value: String, reason: String },
+
+    #[error("Invalid relationship '{value}' for {property}: {reason}")]
+    InvalidRelationship { property: String, value: String, reason: String },
+
+    #[error("Value {value} of {property} violates its validator: {reason}")]
+    ValidatorViolation { property: String, value: String, reason: String },
+
+    #[error("Invalid number {value} for {property}: {reason}")]
+    InvalidNumber { property: String, value: String, reason: String },
+
+    #[error("Invalid value {value} for {property}: expected one of {allowed}")]
+    InvalidEnumValue { property: String, value: String, allowed: String },
+
+    #[error("Invalid system property {property} on {class_name}: {reason}")]
+    InvalidSystemProperty { property: String, class_name: String, reason: String },
+
+    #[error("Duplicate identifier {identifier} of {class_name}, first used on line {first_line}")]
+    DuplicateIdentifier { class_name: String, identifier: String, first_line: usize },
+
+    #[error("Dangling reference {reference} in {property}")]
+    DanglingReference { property: String, reference: String },
+
+    #[error("Declaration {declaration} extends itself through {cycle}")]
+    CyclicSupertype { declaration: String, cycle: String },
+}
+
+/// Every code returned by [`ValidationError::code`], in order.
+pub(crate) const CODES: [&str; 54] = [
+    "CV0001", "CV0002", "CV0003", "CV0004", "CV0005", "CV0006", "CV0007", "CV0008", "CV0009",
+    "CV0010", "CV0011", "CV0012", "CV0013", "CV0014", "CV0015", "CV0016", "CV0017", "CV0018",
+    "CV0019", "CV0020", "CV0021", "CV0022", "CV0023", "CV0024", "CV0025", "CV0026", "CV0027",
+    "CV0028", "CV0029", "CV0030", "CV0031", "CV0032", "CV0033", "CV0034", "CV0035", "CV0036",
+    "CV0037", "CV0038", "CV0039", "CV0040", "CV0041", "CV0042", "CV0043", "CV0044", "CV0045",
+    "CV0046", "CV0047", "CV0048", "CV0049", "CV0050", "CV0051", "CV0052", "CV0053", "CV0054",
+];
+
+impl ValidationError {
+    /// The stable code of the error, e.g. `CV0030` for [`UndeclaredType`](ValidationError::UndeclaredType).
+    /// Codes are never reused, see [`explain`](crate::explain::explain) for their long-form explanation.
+    pub fn code(&self) -> &'static str {
+        use ValidationError::*;
+        match self {
+            JsonError(_) => "CV0001",
+            IoError(_) => "CV0002",
+            ValidationFailed { .. } => "CV0003",
+            TypeMismatch { .. } => "CV0004",
+            UnexpectedType { .. } => "CV0005",
+            MissingRequiredProperty { .. } => "CV0006",
+            InvalidPropertyValue { .. } => "CV0007",
+            UnknownClass { .. } => "CV0008",
+            UnknownProperty { .. } => "CV0009",
+            MetamodelError { .. } => "CV0010",
+            UnknownError => "CV0011",
+            StringValidationError { .. } => "CV0012",
+            MissingTypeDefinition { .. } => "CV0013",
+            MissingSuperTypeDefinition { .. } => "CV0014",
+            Generic { .. } => "CV0015",
+            ValidatorInitializationError => "CV0016",
+            InvalidIdentifier { .. } => "CV0017",
+            ReservedIdentifier { .. } => "CV0018",
+            InvalidNamespace { .. } => "CV0019",
+            InvalidNamespaceVersion { .. } => "CV0020",
+            UnversionedNamespace { .. } => "CV0021",
+            InvalidValidatorRange { .. } => "CV0022",
+            InvalidValidatorBound { .. } => "CV0023",
+            InvalidRegexPattern { .. } => "CV0024",
+            InvalidDefaultValue { .. } => "CV0025",
+            InvalidEnumDeclaration { .. } => "CV0026",
+            DuplicateEnumValue { .. } => "CV0027",
+            InvalidRelationshipTarget { .. } => "CV0028",
+            UnidentifiedRelationshipTarget { .. } => "CV0029",
+            UndeclaredType { .. } => "CV0030",
+            IncompatibleSupertype { .. } => "CV0031",
+            ScalarSupertype { .. } => "CV0032",
+            UnknownDecorator { .. } => "CV0033",
+            InvalidDecoratorArguments { .. } => "CV0034",
+            InvalidMapDeclaration { .. } => "CV0035",
+            InvalidIdentifyingField { .. } => "CV0036",
+            IdentityRedeclared { .. } => "CV0037",
+            UnusedImport { .. } => "CV0038",
+            UnreferencedDeclaration { .. } => "CV0039",
+            NonPascalCaseDeclaration { .. } => "CV0040",
+            NonCamelCaseProperty { .. } => "CV0041",
+            EmptyConcept { .. } => "CV0042",
+            MissingDescription { .. } => "CV0043",
+            InvalidRuleConfiguration { .. } => "CV0044",
+            AbstractInstance { .. } => "CV0045",
+            InvalidDateTime { .. } => "CV0046",
+            InvalidRelationship { .. } => "CV0047",
+            ValidatorViolation { .. } => "CV0048",
+            InvalidNumber { .. } => "CV0049",
+            InvalidEnumValue { .. } => "CV0050",
+            InvalidSystemProperty { .. } => "CV0051",
+            DuplicateIdentifier { .. } => "CV0052",
+            DanglingReference { .. } => "CV0053",
+            CyclicSupertype { .. } => "CV0054",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes() {
+        let s = String::new;
+        let errors = [
+            ValidationError::JsonError(serde_json::from_str::<serde_json::Value>("").unwrap_err()),
+            ValidationError::IoError(std::io::Error::other("")),
+            ValidationError::ValidationFailed { message: s() },
+            ValidationError::TypeMismatch { expected: s(), found: s() },
+            ValidationError::UnexpectedType { expected: s() },
+            ValidationError::MissingRequiredProperty { property: s() },
+            ValidationError::InvalidPropertyValue { property: s(), value: s() },
+            ValidationError::UnknownClass { class_name: s() },
+            ValidationError::UnknownProperty { property_name: s() },
+            ValidationError::MetamodelError { message: s() },
+            ValidationError::UnknownError,
+            ValidationError::StringValidationError { message: s() },
+            ValidationError::MissingTypeDefinition { name: s() },
+            ValidationError::MissingSuperTypeDefinition { name: s() },
+            ValidationError::Generic { message: s() },
+            ValidationError::ValidatorInitializationError,
+            ValidationError::InvalidIdentifier { name: s(), context: s() },
+            ValidationError::ReservedIdentifier { name: s(), context: s() },
+            ValidationError::InvalidNamespace { namespace: s(), reason: s() },
+            ValidationError::InvalidNamespaceVersion { namespace: s(), reason: s() },
+            ValidationError::UnversionedNamespace { namespace: s() },
+            ValidationError::InvalidValidatorRange { context: s(), lower: s(), upper: s() },
+            ValidationError::InvalidValidatorBound { context: s(), bound: s(), reason: s() },
+            ValidationError::InvalidRegexPattern { context: s(), pattern: s(), reason: s() },
+            ValidationError::InvalidDefaultValue { context: s(), value: s(), reason: s() },
+            ValidationError::InvalidEnumDeclaration { name: s(), reason: s() },
+            ValidationError::DuplicateEnumValue { name: s(), value: s() },
+            ValidationError::InvalidRelationshipTarget { context: s(), target: s(), reason: s() },
+            ValidationError::UnidentifiedRelationshipTarget { context: s(), target: s() },
+            ValidationError::UndeclaredType { context: s(), name: s() },
+            ValidationError::IncompatibleSupertype { declaration: s(), kind: s(), super_type: s(), super_kind: s() },
+            ValidationError::ScalarSupertype { declaration: s(), scalar: s() },
+            ValidationError::UnknownDecorator { name: s(), context: s() },
+            ValidationError::InvalidDecoratorArguments { name: s(), context: s(), reason: s() },
+            ValidationError::InvalidMapDeclaration { name: s(), reason: s() },
+            ValidationError::InvalidIdentifyingField { declaration: s(), field: s(), reason: s() },
+            ValidationError::IdentityRedeclared { declaration: s(), ancestor: s() },
+            ValidationError::UnusedImport { namespace: s(), name: s() },
+            ValidationError::UnreferencedDeclaration { name: s() },
+            ValidationError::NonPascalCaseDeclaration { name: s() },
+            ValidationError::NonCamelCaseProperty { context: s() },
+            ValidationError::EmptyConcept { name: s() },
+            ValidationError::MissingDescription { context: s(), decorator: s() },
+            ValidationError::InvalidRuleConfiguration { reason: s() },
+            ValidationError::AbstractInstance { class_name: s() },
+            ValidationError::InvalidDateTime { property: s(), value: s(), reason: s() },
+            ValidationError::InvalidRelationship { property: s(), value: s(), reason: s() },
+            ValidationError::ValidatorViolation { property: s(), value: s(), reason: s() },
+            ValidationError::InvalidNumber { property: s(), value: s(), reason: s() },
+            ValidationError::InvalidEnumValue { property: s(), value: s(), allowed: s() },
+            ValidationError::InvalidSystemProperty { property: s(), class_name: s(), reason: s() },
+            ValidationError::DuplicateIdentifier { class_name: s(), identifier: s(), first_line: 0 },
+            ValidationError::DanglingReference { property: s(), reference: s() },
+            ValidationError::CyclicSupertype { declaration: s(), cycle: s() },
+        ];
+        assert_eq!(errors.iter().map(ValidationError::code).collect::<Vec<_>>(), CODES);
+    }
 }
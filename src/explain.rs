@@ -0,0 +1,109 @@
+//! Long-form explanations of the [error codes](crate::ValidationError::code).
+//! Each explanation is a markdown document in `src/error_codes`, compiled into the crate.
+
+use crate::error::CODES;
+
+/// The explanations, by code.
+const CATALOGUE: [(&str, &str); 54] = [
+    ("CV0001", include_str!("error_codes/CV0001.md")),
+    ("CV0002", include_str!("error_codes/CV0002.md")),
+    ("CV0003", include_str!("error_codes/CV0003.md")),
+    ("CV0004", include_str!("error_codes/CV0004.md")),
+    ("CV0005", include_str!("error_codes/CV0005.md")),
+    ("CV0006", include_str!("error_codes/CV0006.md")),
+    ("CV0007", include_str!("error_codes/CV0007.md")),
+    ("CV0008", include_str!("error_codes/CV0008.md")),
+    ("CV0009", include_str!("error_codes/CV0009.md")),
+    ("CV0010", include_str!("error_codes/CV0010.md")),
+    ("CV0011", include_str!("error_codes/CV0011.md")),
+    ("CV0012", include_str!("error_codes/CV0012.md")),
+    ("CV0013", include_str!("error_codes/CV0013.md")),
+    ("CV0014", include_str!("error_codes/CV0014.md")),
+    ("CV0015", include_str!("error_codes/CV0015.md")),
+    ("CV0016", include_str!("error_codes/CV0016.md")),
+    ("CV0017", include_str!("error_codes/CV0017.md")),
+    ("CV0018", include_str!("error_codes/CV0018.md")),
+    ("CV0019", include_str!("error_codes/CV0019.md")),
+    ("CV0020", include_str!("error_codes/CV0020.md")),
+    ("CV0021", include_str!("error_codes/CV0021.md")),
+    ("CV0022", include_str!("error_codes/CV0022.md")),
+    ("CV0023", include_str!("error_codes/CV0023.md")),
+    ("CV0024", include_str!("error_codes/CV0024.md")),
+    ("CV0025", include_str!("error_codes/CV0025.md")),
+    ("CV0026", include_str!("error_codes/CV0026.md")),
+    ("CV0027", include_str!("error_codes/CV0027.md")),
+    ("CV0028", include_str!("error_codes/CV0028.md")),
+    ("CV0029", include_str!("error_codes/CV0029.md")),
+    ("CV0030", include_str!("error_codes/CV0030.md")),
+    ("CV0031", include_str!("error_codes/CV0031.md")),
+    ("CV0032", include_str!("error_codes/CV0032.md")),
+    ("CV0033", include_str!("error_codes/CV0033.md")),
+    ("CV0034", include_str!("error_codes/CV0034.md")),
+    ("CV0035", include_str!("error_codes/CV0035.md")),
+    ("CV0036", include_str!("error_codes/CV0036.md")),
+    ("CV0037", include_str!("error_codes/CV0037.md")),
+    ("CV0038", include_str!("error_codes/CV0038.md")),
+    ("CV0039", include_str!("error_codes/CV0039.md")),
+    ("CV0040", include_str!("error_codes/CV0040.md")),
+    ("CV0041", include_str!("error_codes/CV0041.md")),
+    ("CV0042", include_str!("error_codes/CV0042.md")),
+    ("CV0043", include_str!("error_codes/CV0043.md")),
+    ("CV0044", include_str!("error_codes/CV0044.md")),
+    ("CV0045", include_str!("error_codes/CV0045.md")),
+    ("CV0046", include_str!("error_codes/CV0046.md")),
+    ("CV0047", include_str!("error_codes/CV0047.md")),
+    ("CV0048", include_str!("error_codes/CV0048.md")),
+    ("CV0049", include_str!("error_codes/CV0049.md")),
+    ("CV0050", include_str!("error_codes/CV0050.md")),
+    ("CV0051", include_str!("error_codes/CV0051.md")),
+    ("CV0052", include_str!("error_codes/CV0052.md")),
+    ("CV0053", include_str!("error_codes/CV0053.md")),
+    ("CV0054", include_str!("error_codes/CV0054.md")),
+];
+
+/// Returns the explanation of an error code such as `CV0030`, ignoring case.
+pub fn explain(code: &str) -> Option<&'static str> {
+    CATALOGUE
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(code))
+        .map(|(_, explanation)| *explanation)
+}
+
+/// All the error codes, in order.
+pub fn codes() -> impl Iterator<Item = &'static str> {
+    CODES.into_iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ValidationError;
+
+    #[test]
+    fn test_catalogue_is_consistent() {
+        for (index, (code, explanation)) in CATALOGUE.iter().enumerate() {
+            assert_eq!(*code, format!("CV{:04}", index + 1));
+            assert!(explanation.starts_with(&format!("# {}: ", code)), "{}", code);
+        }
+    }
+
+    #[test]
+    fn test_explain() {
+        let error = ValidationError::UndeclaredType {
+            context: "Person.address".to_string(),
+            name: "Address".to_string(),
+        };
+        assert_eq!(error.code(), "CV0030");
+        assert!(explain(error.code()).unwrap().contains("Undeclared type"));
+        assert_eq!(explain("cv0030"), explain("CV0030"));
+        assert!(explain("CV9999").is_none());
+
+        let error = ValidationError::CyclicSupertype { declaration: String::new(), cycle: String::new() };
+        assert_eq!(Some(error.code()), codes().last());
+    }
+
+    #[test]
+    fn test_every_code_is_explained() {
+        assert_eq!(CATALOGUE.iter().map(|(code, _)| *code).collect::<Vec<_>>(), CODES);
+    }
+}
@@ -0,0 +1,867 @@
+//! Validation of instances, i.e. business data, against user models.
+//!
+//! It plays the role of `Serializer.fromJSON` from the JS implementation:
+//! models are registered in a [`ModelManager`](crate::model_manager::ModelManager)
+//! next to the metamodel, and instances are checked against their `$class`,
+//! with the same machinery used to validate models against the metamodel.
+
+use serde_json::Value;
+
+use crate::diagnostics::Diagnostic;
+use crate::error::ValidationResult;
+use crate::model_manager::ModelManager;
+use crate::options::InstanceOptions;
+use crate::semantic;
+use crate::validator::ValidationOptions;
+
+/// Validates instances against the models registered with [`add_model`](InstanceValidator::add_model).
+pub struct InstanceValidator {
+    model_manager: ModelManager,
+}
+
+impl InstanceValidator {
+    pub fn new() -> ValidationResult<Self> {
+        Self::with_options(InstanceOptions::default())
+    }
+
+    pub fn with_options(options: InstanceOptions) -> ValidationResult<Self> {
+        let mut model_manager = ModelManager::new()?;
+        model_manager.set_instance_options(options);
+        Ok(Self { model_manager })
+    }
+
+    /// Validates a `Model` or `Models` AST and registers its declarations.
+    /// Models importing types from other models must be added after them,
+    /// or together in a `Models` AST. `@Suppress` decorators in the model only apply to lint rules:
+    /// the checks that are errors by default keep the registered types consistent.
+    pub fn add_model(&mut self, json_ast: &str) -> ValidationResult<()> {
+        let ast: Value = serde_json::from_str(json_ast)?;
+        self.model_manager.validate_metamodel(&ast)?;
+        if let Some(diagnostic) = semantic::validate(&ast, &ValidationOptions::for_instances())
+            .into_iter()
+            .find(Diagnostic::is_error)
+        {
+            return Err(diagnostic.error);
+        }
+        self.model_manager.add_model(&ast)
+    }
+
+    /// Validates an instance JSON document against the type named by its `$class`.
+    pub fn validate(&self, json: &str) -> ValidationResult<()> {
+        let instance: Value = serde_json::from_str(json)?;
+        self.validate_value(&instance)
+    }
+
+    /// Validates an already parsed instance.
+    pub fn validate_value(&self, instance: &Value) -> ValidationResult<()> {
+        self.model_manager.validate_instance(instance)
+    }
+
+    pub(crate) fn model_manager(&self) -> &ModelManager {
+        &self.model_manager
+    }
+
+    /// Validates an instance and returns its canonical form with the diagnostics:
+    /// missing properties with a `defaultValue` are filled in, `$identifier` is set from
+    /// the identifying field, and `DateTime` values are converted to UTC, e.g.
+    /// `2024-01-31T10:00:00+02:00` becomes `2024-01-31T08:00:00.000Z`.
+    /// Invalid instances are returned unchanged, with an error diagnostic.
+    pub fn normalize(&self, instance: &Value) -> (Value, Vec<Diagnostic>) {
+        match self.model_manager.normalize_instance(instance) {
+            Ok(normalized) => (normalized, vec![]),
+            Err(error) => (instance.clone(), vec![Diagnostic::error(error)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ValidationError;
+    use crate::validator::Validator;
+    use serde_json::json;
+
+    fn property(class: &str, name: &str, is_array: bool, is_optional: bool) -> Value {
+        json!({
+            "$class": format!("concerto.metamodel@1.0.0.{}", class),
+            "name": name,
+            "isArray": is_array,
+            "isOptional": is_optional
+        })
+    }
+
+    fn object_property(name: &str, type_name: &str, is_optional: bool) -> Value {
+        let mut property = property("ObjectProperty", name, false, is_optional);
+        property["type"] = json!({ "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": type_name });
+        property
+    }
+
+    fn concept(name: &str, is_abstract: bool, super_type: Option<&str>, properties: Value) -> Value {
+        let mut concept = json!({
+            "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+            "name": name,
+            "isAbstract": is_abstract,
+            "properties": properties
+        });
+        if let Some(super_type) = super_type {
+            concept["superType"] = json!({ "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": super_type });
+        }
+        concept
+    }
+
+    fn validator() -> InstanceValidator {
+        let common = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.common@1.0.0",
+            "declarations": [
+                concept("Address", false, None, json!([property("StringProperty", "city", false, false)])),
+                {
+                    "$class": "concerto.metamodel@1.0.0.StringScalar",
+                    "name": "Email",
+                    "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": "^[a-z]+@[a-z.]+$", "flags": "i" }
+                }
+            ]
+        });
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "imports": [
+                { "$class": "concerto.metamodel@1.0.0.ImportTypes", "namespace": "org.common@1.0.0", "types": ["Address", "Email"] }
+            ],
+            "declarations": [
+                concept("Party", true, None, json!([property("StringProperty", "name", false, false)])),
+                concept("Person", false, Some("Party"), json!([
+                    property("IntegerProperty", "age", false, true),
+                    property("StringProperty", "nicknames", true, true),
+                    object_property("address", "Address", true),
+                    object_property("email", "Email", true)
+                ])),
+                concept("Employee", false, Some("Person"), json!([property("BooleanProperty", "manager", false, false)])),
+                concept("Team", false, None, json!([object_property("lead", "Person", false)]))
+            ]
+        });
+
+        let mut validator = InstanceValidator::new().unwrap();
+        validator.add_model(&common.to_string()).unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+        validator
+    }
+
+    fn person(properties: Value) -> Value {
+        let mut person = json!({ "$class": "org.example@1.0.0.Person", "name": "Ada" });
+        person.as_object_mut().unwrap().extend(properties.as_object().unwrap().clone());
+        person
+    }
+
+    #[test]
+    fn test_valid_instances() {
+        let validator = validator();
+        let instance = person(json!({
+            "age": 36,
+            "nicknames": ["Countess"],
+            "address": { "$class": "org.common@1.0.0.Address", "city": "London" },
+            "email": "ADA@example.org"
+        }));
+        validator.validate_value(&instance).unwrap();
+        validator.validate_value(&person(json!({}))).unwrap();
+
+        let team = json!({
+            "$class": "org.example@1.0.0.Team",
+            "lead": { "$class": "org.example@1.0.0.Employee", "name": "Grace", "manager": true }
+        });
+        validator.validate_value(&team).unwrap();
+    }
+
+    #[test]
+    fn test_fields() {
+        let validator = validator();
+        let missing = json!({ "$class": "org.example@1.0.0.Person" });
+        assert!(matches!(validator.validate_value(&missing), Err(ValidationError::MissingRequiredProperty { property }) if property == "name"));
+
+        let unknown = person(json!({ "height": 1.7 }));
+        assert!(matches!(validator.validate_value(&unknown), Err(ValidationError::UnknownProperty { .. })));
+
+        assert!(validator.validate_value(&person(json!({ "nicknames": "Countess" }))).is_err());
+        assert!(validator.validate_value(&person(json!({ "nicknames": [1] }))).is_err());
+        assert!(validator.validate_value(&person(json!({ "age": "36" }))).is_err());
+        assert!(validator.validate_value(&person(json!({ "email": "not an email" }))).is_err());
+    }
+
+    #[test]
+    fn test_classes() {
+        let validator = validator();
+        let abstract_party = json!({ "$class": "org.example@1.0.0.Party", "name": "Ada" });
+        assert!(matches!(validator.validate_value(&abstract_party), Err(ValidationError::AbstractInstance { .. })));
+
+        let unknown_class = json!({ "$class": "org.example@1.0.0.Robot" });
+        assert!(validator.validate_value(&unknown_class).is_err());
+
+        let wrong_nested_class = person(json!({ "address": { "$class": "org.example@1.0.0.Person", "name": "Bob" } }));
+        assert!(matches!(validator.validate_value(&wrong_nested_class), Err(ValidationError::TypeMismatch { .. })));
+
+        let nested_error = person(json!({ "address": { "$class": "org.common@1.0.0.Address" } }));
+        assert!(matches!(validator.validate_value(&nested_error), Err(ValidationError::MissingRequiredProperty { .. })));
+    }
+
+    #[test]
+    fn test_invalid_models_are_rejected() {
+        let mut validator = InstanceValidator::new().unwrap();
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [concept("Person", false, None, json!([object_property("address", "Address", false)]))]
+        });
+        assert!(matches!(validator.add_model(&model.to_string()), Err(ValidationError::MissingTypeDefinition { .. })));
+
+        let imports_unregistered_model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "imports": [{ "$class": "concerto.metamodel@1.0.0.ImportType", "namespace": "org.common@1.0.0", "name": "Address" }],
+            "declarations": [concept("Person", false, None, json!([object_property("address", "Address", false)]))]
+        });
+        assert!(validator.add_model(&imports_unregistered_model.to_string()).is_err());
+    }
+
+    fn date_time_validator(options: InstanceOptions) -> InstanceValidator {
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [
+                { "$class": "concerto.metamodel@1.0.0.DateTimeScalar", "name": "Day" },
+                concept("Meeting", false, None, json!([
+                    property("DateTimeProperty", "start", false, false),
+                    object_property("day", "Day", true)
+                ]))
+            ]
+        });
+        let mut validator = InstanceValidator::with_options(options).unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+        validator
+    }
+
+    fn meeting(start: &str) -> Value {
+        json!({ "$class": "org.example@1.0.0.Meeting", "start": start })
+    }
+
+    #[test]
+    fn test_date_times() {
+        let validator = date_time_validator(InstanceOptions::default());
+        validator.validate_value(&meeting("2024-02-29T10:00:00Z")).unwrap();
+        validator.validate_value(&meeting("2024-02-29T10:00:00.123+02:00")).unwrap();
+
+        for invalid in ["2023-02-29T10:00:00Z", "2024-02-29T25:00:00Z", "2024-02-29T10:00:00", "2024-02-29", "29/02/2024"] {
+            let result = validator.validate_value(&meeting(invalid));
+            assert!(matches!(result, Err(ValidationError::InvalidDateTime { .. })), "{}: {:?}", invalid, result);
+        }
+        assert!(validator.validate_value(&json!({ "$class": "org.example@1.0.0.Meeting", "start": 0 })).is_err());
+
+        let scalar = json!({ "$class": "org.example@1.0.0.Meeting", "start": "2024-02-29T10:00:00Z", "day": "2024-02-30T00:00:00Z" });
+        assert!(matches!(validator.validate_value(&scalar), Err(ValidationError::InvalidDateTime { .. })));
+    }
+
+    #[test]
+    fn test_date_time_options() {
+        let date_only = date_time_validator(InstanceOptions::default().allow_date_only(true));
+        date_only.validate_value(&meeting("2024-02-29")).unwrap();
+        assert!(date_only.validate_value(&meeting("2023-02-29")).is_err());
+
+        let utc = date_time_validator(InstanceOptions::default().require_utc(true));
+        utc.validate_value(&meeting("2024-02-29T10:00:00Z")).unwrap();
+        utc.validate_value(&meeting("2024-02-29T10:00:00+00:00")).unwrap();
+        assert!(matches!(utc.validate_value(&meeting("2024-02-29T10:00:00+02:00")), Err(ValidationError::InvalidDateTime { .. })));
+    }
+
+    fn relationship_validator() -> InstanceValidator {
+        let identified = |class: &str, name: &str, super_type: Option<&str>, properties: Value| {
+            let mut declaration = concept(name, false, super_type, properties);
+            declaration["$class"] = json!(format!("concerto.metamodel@1.0.0.{}", class));
+            if super_type.is_none() {
+                declaration["identified"] = json!({ "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": "id" });
+            }
+            declaration
+        };
+        let relationship = |name: &str, type_name: &str, is_array: bool, is_optional: bool| {
+            let mut relationship = property("RelationshipProperty", name, is_array, is_optional);
+            relationship["type"] = json!({ "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": type_name });
+            relationship
+        };
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.acme@1.0.0",
+            "declarations": [
+                identified("ParticipantDeclaration", "Driver", None, json!([property("StringProperty", "id", false, false)])),
+                identified("ParticipantDeclaration", "TruckDriver", Some("Driver"), json!([])),
+                identified("AssetDeclaration", "Vehicle", None, json!([
+                    property("StringProperty", "id", false, false),
+                    relationship("driver", "Driver", false, false),
+                    relationship("passengers", "Driver", true, true)
+                ]))
+            ]
+        });
+        let mut validator = InstanceValidator::new().unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+        validator
+    }
+
+    fn vehicle(driver: &str) -> Value {
+        json!({ "$class": "org.acme@1.0.0.Vehicle", "id": "V-1", "driver": driver })
+    }
+
+    #[test]
+    fn test_relationships() {
+        let validator = relationship_validator();
+        for driver in [
+            "resource:org.acme@1.0.0.Driver#D-123",
+            "resource:org.acme@1.0.0.TruckDriver#D-123",
+            "resource:org.acme.Driver#jane%40example.org",
+            "D-123",
+        ] {
+            validator.validate_value(&vehicle(driver)).unwrap();
+        }
+
+        let mut passengers = vehicle("D-1");
+        passengers["passengers"] = json!(["resource:org.acme@1.0.0.Driver#D-2", "D-3"]);
+        validator.validate_value(&passengers).unwrap();
+
+        for driver in [
+            "resource:org.acme@1.0.0.Vehicle#V-1",
+            "resource:org.acme@1.0.0.Pilot#P-1",
+            "resource:org.acme@2.0.0.Driver#D-1",
+            "resource:org.acme@1.0.0.Driver#",
+            "resource:org.acme@1.0.0.Driver",
+        ] {
+            let result = validator.validate_value(&vehicle(driver));
+            assert!(matches!(result, Err(ValidationError::InvalidRelationship { ref property, .. }) if property == "driver"), "{}: {:?}", driver, result);
+        }
+
+        passengers["passengers"] = json!(["D-2", "resource:org.acme@1.0.0.Vehicle#V-1"]);
+        assert!(matches!(validator.validate_value(&passengers), Err(ValidationError::InvalidRelationship { .. })));
+        let mut not_a_string = vehicle("D-1");
+        not_a_string["driver"] = json!({ "$class": "org.acme@1.0.0.Driver", "id": "D-1" });
+        assert!(validator.validate_value(&not_a_string).is_err());
+    }
+
+    #[test]
+    fn test_unversioned_relationship_namespaces() {
+        let type_identifier = |namespace: &str, name: &str| json!({ "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "namespace": namespace, "name": name });
+        let participant = |name: &str, super_type: Value| {
+            let mut declaration = json!({ "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration", "name": name, "isAbstract": false, "properties": [] });
+            if super_type.is_null() {
+                declaration["identified"] = json!({ "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": "id" });
+                declaration["properties"] = json!([property("StringProperty", "id", false, false)]);
+            } else {
+                declaration["superType"] = super_type;
+            }
+            declaration
+        };
+        let mut lead = property("RelationshipProperty", "lead", false, false);
+        lead["type"] = type_identifier("org.base@1.0.0", "Person");
+        let mut driver = property("RelationshipProperty", "driver", false, false);
+        driver["type"] = type_identifier("org.acme@2.0.0", "Manager");
+        let model = |namespace: &str, declarations: Value| {
+            json!({ "$class": "concerto.metamodel@1.0.0.Model", "namespace": namespace, "declarations": declarations }).to_string()
+        };
+
+        let mut validator = InstanceValidator::new().unwrap();
+        validator
+            .add_model(&model("org.base@1.0.0", json!([participant("Person", Value::Null), concept("Team", false, None, json!([lead]))])))
+            .unwrap();
+        validator
+            .add_model(&model("org.acme@1.0.0", json!([participant("Manager", type_identifier("org.base@1.0.0", "Person"))])))
+            .unwrap();
+        validator
+            .add_model(&model(
+                "org.acme@2.0.0",
+                json!([participant("Manager", type_identifier("org.base@1.0.0", "Person")), concept("Truck", false, None, json!([driver]))]),
+            ))
+            .unwrap();
+
+        // The version of the declared type is preferred.
+        validator
+            .validate_value(&json!({ "$class": "org.acme@2.0.0.Truck", "driver": "resource:org.acme.Manager#M-1" }))
+            .unwrap();
+
+        // Otherwise an unversioned namespace matching several versions is ambiguous.
+        let team = json!({ "$class": "org.base@1.0.0.Team", "lead": "resource:org.acme.Manager#M-1" });
+        let result = validator.validate_value(&team);
+        assert!(
+            matches!(&result, Err(ValidationError::InvalidRelationship { reason, .. })
+                if reason == "type org.acme.Manager is ambiguous, it matches org.acme@1.0.0.Manager, org.acme@2.0.0.Manager"),
+            "{:?}",
+            result
+        );
+        validator
+            .validate_value(&json!({ "$class": "org.base@1.0.0.Team", "lead": "resource:org.acme@1.0.0.Manager#M-1" }))
+            .unwrap();
+    }
+
+    fn bounded_validator(options: InstanceOptions) -> InstanceValidator {
+        let bounded = |class: &str, name: &str, lower: Value, upper: Value| {
+            let mut property = property(&format!("{}Property", class), name, false, true);
+            property["validator"] = json!({ "$class": format!("concerto.metamodel@1.0.0.{}DomainValidator", class), "lower": lower });
+            if !upper.is_null() {
+                property["validator"]["upper"] = upper;
+            }
+            property
+        };
+        let mut code = property("StringProperty", "code", false, true);
+        code["lengthValidator"] = json!({ "$class": "concerto.metamodel@1.0.0.StringLengthValidator", "minLength": 2, "maxLength": 3 });
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.IntegerScalar",
+                    "name": "Percentage",
+                    "validator": { "$class": "concerto.metamodel@1.0.0.IntegerDomainValidator", "lower": 0, "upper": 100 }
+                },
+                {
+                    "$class": "concerto.metamodel@1.0.0.StringScalar",
+                    "name": "Initials",
+                    "lengthValidator": { "$class": "concerto.metamodel@1.0.0.StringLengthValidator", "maxLength": 2 }
+                },
+                concept("Measure", false, None, json!([
+                    bounded("Integer", "age", json!(0), json!(150)),
+                    bounded("Long", "population", json!(1), Value::Null),
+                    bounded("Double", "ratio", json!(-1.5), json!(1.5)),
+                    code,
+                    object_property("share", "Percentage", true),
+                    object_property("initials", "Initials", true)
+                ]))
+            ]
+        });
+        let mut validator = InstanceValidator::with_options(options).unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+        validator
+    }
+
+    fn measure(properties: Value) -> Value {
+        let mut measure = json!({ "$class": "org.example@1.0.0.Measure" });
+        measure.as_object_mut().unwrap().extend(properties.as_object().unwrap().clone());
+        measure
+    }
+
+    #[test]
+    fn test_validator_bounds() {
+        let validator = bounded_validator(InstanceOptions::default());
+        validator
+            .validate_value(&measure(json!({ "age": 150, "population": 1, "ratio": -1.5, "code": "ABC", "share": 0, "initials": "AL" })))
+            .unwrap();
+        validator.validate_value(&measure(json!({ "population": 8_000_000_000i64, "code": "éé" }))).unwrap();
+
+        for (properties, reason) in [
+            (json!({ "age": 151 }), "151 is greater than upper bound 150"),
+            (json!({ "age": -1 }), "-1 is less than lower bound 0"),
+            (json!({ "population": 0 }), "0 is less than lower bound 1"),
+            (json!({ "ratio": 1.75 }), "1.75 is greater than upper bound 1.5"),
+            (json!({ "code": "A" }), "length 1 is less than minLength 2"),
+            (json!({ "code": "ABCD" }), "length 4 is greater than maxLength 3"),
+            (json!({ "share": 101 }), "101 is greater than upper bound 100"),
+            (json!({ "initials": "ALA" }), "length 3 is greater than maxLength 2"),
+        ] {
+            let result = validator.validate_value(&measure(properties.clone()));
+            assert!(
+                matches!(&result, Err(ValidationError::ValidatorViolation { reason: found, .. }) if found == reason),
+                "{}: {:?}",
+                properties,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        let validator = bounded_validator(InstanceOptions::default());
+        let measure = |properties: &str| format!(r#"{{ "$class": "org.example@1.0.0.Measure", {} }}"#, properties);
+        for valid in [
+            r#""age": 36.0"#,
+            r#""age": 1e2"#,
+            r#""population": 9223372036854775807"#,
+            r#""ratio": 1"#,
+            r#""ratio": 0.1000000000000000000000001"#,
+        ] {
+            validator.validate(&measure(valid)).unwrap();
+        }
+
+        for (invalid, reason) in [
+            (r#""age": 36.5"#, "36.5 is not an integral number"),
+            (r#""age": 2147483648"#, "2147483648 is outside the range of a 32-bit Integer, -2147483648 to 2147483647"),
+            (r#""population": 9223372036854775808"#, "9223372036854775808 is outside the range of a 64-bit Long, -9223372036854775808 to 9223372036854775807"),
+            (r#""population": 1e400"#, "1e400 is too large for an integral type"),
+            (r#""ratio": 1e400"#, "outside the range of a 64-bit Double"),
+        ] {
+            let result = validator.validate(&measure(invalid));
+            assert!(matches!(&result, Err(ValidationError::InvalidNumber { reason: found, .. }) if found == reason), "{}: {:?}", invalid, result);
+        }
+
+        let strict = bounded_validator(InstanceOptions::default().strict_integers(true));
+        strict.validate(&measure(r#""age": 36, "ratio": 1.0"#)).unwrap();
+        assert!(matches!(strict.validate(&measure(r#""age": 36.0"#)), Err(ValidationError::InvalidNumber { .. })));
+        assert!(matches!(strict.validate(&measure(r#""population": 1e3"#)), Err(ValidationError::InvalidNumber { .. })));
+    }
+
+    #[test]
+    fn test_decimal_bounds() {
+        let mut age = property("IntegerProperty", "age", false, false);
+        age["validator"] = json!({ "$class": "concerto.metamodel@1.0.0.IntegerDomainValidator", "lower": 0.0, "upper": 1.5e2 });
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [concept("Person", false, None, json!([age]))]
+        });
+        let mut validator = InstanceValidator::new().unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+
+        let person = |age: i64| json!({ "$class": "org.example@1.0.0.Person", "age": age });
+        validator.validate_value(&person(150)).unwrap();
+        let result = validator.validate_value(&person(151));
+        assert!(matches!(&result, Err(ValidationError::ValidatorViolation { reason, .. }) if reason == "151 is greater than upper bound 150"), "{:?}", result);
+        assert!(matches!(validator.validate_value(&person(-1)), Err(ValidationError::ValidatorViolation { .. })));
+    }
+
+    #[test]
+    fn test_enums() {
+        let enum_value = |name: &str| json!({ "$class": "concerto.metamodel@1.0.0.EnumProperty", "name": name });
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [
+                { "$class": "concerto.metamodel@1.0.0.EnumDeclaration", "name": "Color", "properties": [enum_value("RED"), enum_value("GREEN")] },
+                {
+                    "$class": "concerto.metamodel@1.0.0.MapDeclaration",
+                    "name": "Palette",
+                    "key": { "$class": "concerto.metamodel@1.0.0.StringMapKeyType" },
+                    "value": {
+                        "$class": "concerto.metamodel@1.0.0.ObjectMapValueType",
+                        "type": { "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": "Color" }
+                    }
+                },
+                concept("Car", false, None, json!([object_property("color", "Color", false), {
+                    "$class": "concerto.metamodel@1.0.0.ObjectProperty",
+                    "name": "trims",
+                    "type": { "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": "Color" },
+                    "isArray": true,
+                    "isOptional": true
+                }, object_property("palette", "Palette", true)]))
+            ]
+        });
+        let mut validator = InstanceValidator::new().unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+        let car = |color: Value, trims: Value| json!({ "$class": "org.example@1.0.0.Car", "color": color, "trims": trims });
+
+        validator.validate_value(&car(json!("RED"), json!(["GREEN", "RED"]))).unwrap();
+        validator.validate_value(&car(json!("GREEN"), json!([]))).unwrap();
+
+        for (color, trims) in [
+            (json!("BLUE"), json!([])),
+            (json!("red"), json!([])),
+            (json!(0), json!([])),
+            (json!("RED"), json!(["GREEN", "BLUE"])),
+        ] {
+            let result = validator.validate_value(&car(color.clone(), trims));
+            assert!(
+                matches!(&result, Err(ValidationError::InvalidEnumValue { allowed, .. }) if allowed == "RED, GREEN"),
+                "{}: {:?}",
+                color,
+                result
+            );
+        }
+
+        let palette = |entries: Value| {
+            let mut car = car(json!("RED"), json!([]));
+            car["palette"] = json!({ "$class": "org.example@1.0.0.Palette" });
+            car["palette"].as_object_mut().unwrap().extend(entries.as_object().unwrap().clone());
+            car
+        };
+        validator.validate_value(&palette(json!({ "roof": "GREEN", "doors": "RED" }))).unwrap();
+        let result = validator.validate_value(&palette(json!({ "roof": "GREEN", "doors": "BLUE" })));
+        assert!(
+            matches!(&result, Err(ValidationError::InvalidEnumValue { property, allowed, .. }) if property == "palette[doors]" && allowed == "RED, GREEN"),
+            "{:?}",
+            result
+        );
+    }
+
+    fn map_validator() -> InstanceValidator {
+        let type_identifier = |name: &str| json!({ "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": name });
+        let map = |name: &str, key: Value, value: Value| json!({ "$class": "concerto.metamodel@1.0.0.MapDeclaration", "name": name, "key": key, "value": value });
+        let entry = |class: &str| json!({ "$class": format!("concerto.metamodel@1.0.0.{}", class) });
+        let typed_entry = |class: &str, name: &str| json!({ "$class": format!("concerto.metamodel@1.0.0.{}", class), "type": type_identifier(name) });
+        let mut driver = concept("Driver", false, None, json!([property("StringProperty", "id", false, false)]));
+        driver["$class"] = json!("concerto.metamodel@1.0.0.ParticipantDeclaration");
+        driver["identified"] = json!({ "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": "id" });
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.StringScalar",
+                    "name": "Code",
+                    "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": "^[A-Z]+$", "flags": "" }
+                },
+                {
+                    "$class": "concerto.metamodel@1.0.0.EnumDeclaration",
+                    "name": "Color",
+                    "properties": [{ "$class": "concerto.metamodel@1.0.0.EnumProperty", "name": "RED" }]
+                },
+                driver,
+                concept("Point", false, None, json!([property("IntegerProperty", "x", false, false)])),
+                map("Scores", entry("StringMapKeyType"), entry("IntegerMapValueType")),
+                map("Schedule", entry("DateTimeMapKeyType"), entry("StringMapValueType")),
+                map("Colors", typed_entry("ObjectMapKeyType", "Code"), typed_entry("ObjectMapValueType", "Color")),
+                map("Points", entry("StringMapKeyType"), typed_entry("ObjectMapValueType", "Point")),
+                map("Drivers", entry("StringMapKeyType"), typed_entry("RelationshipMapValueType", "Driver")),
+                concept("Registry", false, None, json!([
+                    object_property("scores", "Scores", true),
+                    object_property("schedule", "Schedule", true),
+                    object_property("colors", "Colors", true),
+                    object_property("points", "Points", true),
+                    object_property("drivers", "Drivers", true)
+                ]))
+            ]
+        });
+        let mut validator = InstanceValidator::new().unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+        validator
+    }
+
+    fn registry(property: &str, map: &str, entries: Value) -> Value {
+        let mut map_value = json!({ "$class": format!("org.example@1.0.0.{}", map) });
+        map_value.as_object_mut().unwrap().extend(entries.as_object().unwrap().clone());
+        json!({ "$class": "org.example@1.0.0.Registry", property: map_value })
+    }
+
+    #[test]
+    fn test_maps() {
+        let validator = map_validator();
+        for instance in [
+            registry("scores", "Scores", json!({ "ada": 1, "grace": 2 })),
+            registry("scores", "Scores", json!({})),
+            registry("schedule", "Schedule", json!({ "2024-01-31T10:00:00Z": "standup" })),
+            registry("colors", "Colors", json!({ "CAR": "RED" })),
+            registry("points", "Points", json!({ "origin": { "$class": "org.example@1.0.0.Point", "x": 0 } })),
+            registry("drivers", "Drivers", json!({ "first": "resource:org.example@1.0.0.Driver#D-1", "second": "D-2" })),
+        ] {
+            validator.validate_value(&instance).unwrap();
+        }
+
+        let invalid = |instance: Value| validator.validate_value(&instance);
+        assert!(matches!(invalid(registry("scores", "Scores", json!({ "ada": "one" }))), Err(ValidationError::UnexpectedType { .. })));
+        assert!(matches!(invalid(registry("scores", "Schedule", json!({}))), Err(ValidationError::TypeMismatch { .. })));
+        assert!(matches!(
+            invalid(json!({ "$class": "org.example@1.0.0.Registry", "scores": { "ada": 1 } })),
+            Err(ValidationError::MissingRequiredProperty { .. })
+        ));
+        assert!(matches!(invalid(registry("schedule", "Schedule", json!({ "tomorrow": "standup" }))), Err(ValidationError::InvalidDateTime { .. })));
+        assert!(matches!(invalid(registry("colors", "Colors", json!({ "car": "RED" }))), Err(ValidationError::StringValidationError { .. })));
+        assert!(matches!(invalid(registry("colors", "Colors", json!({ "CAR": "BLUE" }))), Err(ValidationError::InvalidEnumValue { .. })));
+        assert!(matches!(
+            invalid(registry("points", "Points", json!({ "origin": { "$class": "org.example@1.0.0.Point" } }))),
+            Err(ValidationError::MissingRequiredProperty { .. })
+        ));
+        assert!(matches!(
+            invalid(registry("drivers", "Drivers", json!({ "first": "resource:org.example@1.0.0.Point#P-1" }))),
+            Err(ValidationError::InvalidRelationship { ref property, .. }) if property == "drivers[first]"
+        ));
+    }
+
+    #[test]
+    fn test_models_cannot_suppress_errors() {
+        let mut car = concept("Car", false, Some("Color"), json!([]));
+        car["decorators"] = json!([{
+            "$class": "concerto.metamodel@1.0.0.Decorator",
+            "name": "Suppress",
+            "arguments": [{ "$class": "concerto.metamodel@1.0.0.DecoratorString", "value": "supertype" }]
+        }]);
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [
+                { "$class": "concerto.metamodel@1.0.0.EnumDeclaration", "name": "Color", "properties": [{ "$class": "concerto.metamodel@1.0.0.EnumProperty", "name": "RED" }] },
+                car
+            ]
+        })
+        .to_string();
+        assert!(Validator::new().unwrap().validate(&model).is_ok());
+
+        let mut validator = InstanceValidator::new().unwrap();
+        let result = validator.add_model(&model);
+        assert!(matches!(result, Err(ValidationError::IncompatibleSupertype { .. })), "{:?}", result);
+    }
+
+    #[test]
+    fn test_cyclic_supertypes() {
+        let mut a = concept("A", false, Some("B"), json!([]));
+        a["identified"] = json!({ "$class": "concerto.metamodel@1.0.0.Identified" });
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.acme@1.0.0",
+            "declarations": [a, concept("B", false, Some("A"), json!([]))]
+        });
+        let mut validator = InstanceValidator::new().unwrap();
+        assert!(matches!(validator.add_model(&model.to_string()), Err(ValidationError::CyclicSupertype { .. })));
+
+        // Registered without the semantic checks, the hierarchy is still walked safely.
+        validator.model_manager.add_model(&model).unwrap();
+        let instance = json!({ "$class": "org.acme@1.0.0.A", "$identifier": "a" });
+        let result = validator.validate_value(&instance);
+        assert!(matches!(&result, Err(ValidationError::CyclicSupertype { cycle, .. }) if cycle == "org.acme@1.0.0.A -> org.acme@1.0.0.B -> org.acme@1.0.0.A"), "{:?}", result);
+        assert!(matches!(validator.model_manager.get_identity(&instance), Err(ValidationError::CyclicSupertype { .. })));
+    }
+
+    #[test]
+    fn test_system_properties() {
+        let declaration = |class: &str, name: &str, identified: Value, properties: Value| {
+            let mut declaration = concept(name, false, None, properties);
+            declaration["$class"] = json!(format!("concerto.metamodel@1.0.0.{}", class));
+            if !identified.is_null() {
+                declaration["identified"] = identified;
+            }
+            declaration
+        };
+        let mut gold = concept("Gold", false, Some("Member"), json!([]));
+        gold["$class"] = json!("concerto.metamodel@1.0.0.ParticipantDeclaration");
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [
+                declaration("AssetDeclaration", "Car", json!({ "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": "vin" }), json!([
+                    property("StringProperty", "vin", false, false)
+                ])),
+                declaration("ParticipantDeclaration", "Member", json!({ "$class": "concerto.metamodel@1.0.0.Identified" }), json!([])),
+                gold,
+                declaration("TransactionDeclaration", "Trade", Value::Null, json!([])),
+                declaration("EventDeclaration", "Notice", Value::Null, json!([]))
+            ]
+        });
+        let mut validator = InstanceValidator::new().unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+
+        for valid in [
+            json!({ "$class": "org.example@1.0.0.Car", "vin": "V-1" }),
+            json!({ "$class": "org.example@1.0.0.Car", "vin": "V-1", "$identifier": "V-1" }),
+            json!({ "$class": "org.example@1.0.0.Member", "$identifier": "M-1" }),
+            json!({ "$class": "org.example@1.0.0.Gold", "$identifier": "M-2" }),
+            json!({ "$class": "org.example@1.0.0.Trade", "$timestamp": "2024-01-31T10:00:00Z" }),
+            json!({ "$class": "org.example@1.0.0.Notice", "$timestamp": "2024-01-31T10:00:00Z" }),
+        ] {
+            validator.validate_value(&valid).unwrap();
+        }
+
+        let missing = |instance: Value| validator.validate_value(&instance);
+        assert!(matches!(missing(json!({ "$class": "org.example@1.0.0.Gold" })), Err(ValidationError::MissingRequiredProperty { property }) if property == "$identifier"));
+        assert!(matches!(missing(json!({ "$class": "org.example@1.0.0.Trade" })), Err(ValidationError::MissingRequiredProperty { property }) if property == "$timestamp"));
+
+        for (invalid, property) in [
+            (json!({ "$class": "org.example@1.0.0.Car", "vin": "V-1", "$identifier": "V-2" }), "$identifier"),
+            (json!({ "$class": "org.example@1.0.0.Member", "$identifier": 1 }), "$identifier"),
+            (json!({ "$class": "org.example@1.0.0.Member", "$identifier": "" }), "$identifier"),
+            (json!({ "$class": "org.example@1.0.0.Notice", "$timestamp": "2024-01-31T10:00:00Z", "$identifier": "N-1" }), "$identifier"),
+            (json!({ "$class": "org.example@1.0.0.Member", "$identifier": "M-1", "$timestamp": "2024-01-31T10:00:00Z" }), "$timestamp"),
+            (json!({ "$class": "org.example@1.0.0.Member", "$identifier": "M-1", "$id": "M-1" }), "$id"),
+        ] {
+            let result = validator.validate_value(&invalid);
+            assert!(
+                matches!(&result, Err(ValidationError::InvalidSystemProperty { property: found, .. }) if found == property),
+                "{}: {:?}",
+                invalid,
+                result
+            );
+        }
+        let timestamp = json!({ "$class": "org.example@1.0.0.Trade", "$timestamp": "yesterday" });
+        assert!(matches!(validator.validate_value(&timestamp), Err(ValidationError::InvalidDateTime { .. })));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let with_default = |class: &str, name: &str, default_value: Value| {
+            let mut property = property(class, name, false, true);
+            property["defaultValue"] = default_value;
+            property
+        };
+        let mut share = object_property("share", "Percentage", true);
+        share["defaultValue"] = json!("50");
+        let mut account = concept("Account", false, None, json!([
+            property("StringProperty", "number", false, false),
+            with_default("StringProperty", "currency", json!("EUR")),
+            with_default("IntegerProperty", "limit", json!(100)),
+            object_property("opened", "Day", true),
+            share,
+            object_property("status", "Status", true),
+            property("DateTimeProperty", "closed", false, true),
+            property("DateTimeProperty", "audits", true, true),
+            object_property("holder", "Holder", true)
+        ]));
+        account["$class"] = json!("concerto.metamodel@1.0.0.AssetDeclaration");
+        account["identified"] = json!({ "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": "number" });
+        let model = json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": [
+                { "$class": "concerto.metamodel@1.0.0.DateTimeScalar", "name": "Day", "defaultValue": "2024-01-01T00:00:00+01:00" },
+                { "$class": "concerto.metamodel@1.0.0.IntegerScalar", "name": "Percentage" },
+                {
+                    "$class": "concerto.metamodel@1.0.0.EnumDeclaration",
+                    "name": "Status",
+                    "properties": [{ "$class": "concerto.metamodel@1.0.0.EnumProperty", "name": "OPEN" }]
+                },
+                concept("Holder", false, None, json!([property("DateTimeProperty", "born", false, false)])),
+                account
+            ]
+        });
+        let mut validator = InstanceValidator::new().unwrap();
+        validator.add_model(&model.to_string()).unwrap();
+
+        let instance = json!({
+            "$class": "org.example@1.0.0.Account",
+            "number": "A-1",
+            "limit": 50,
+            "closed": "2024-03-01T01:30:00+02:00",
+            "audits": ["2024-02-01T12:00:00-05:00"],
+            "holder": { "$class": "org.example@1.0.0.Holder", "born": "1815-12-10T00:00:00+00:30" }
+        });
+        let (normalized, diagnostics) = validator.normalize(&instance);
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(
+            normalized,
+            json!({
+                "$class": "org.example@1.0.0.Account",
+                "$identifier": "A-1",
+                "number": "A-1",
+                "currency": "EUR",
+                "limit": 50,
+                "opened": "2023-12-31T23:00:00.000Z",
+                "share": 50,
+                "closed": "2024-02-29T23:30:00.000Z",
+                "audits": ["2024-02-01T17:00:00.000Z"],
+                "holder": { "$class": "org.example@1.0.0.Holder", "born": "1815-12-09T23:30:00.000Z" }
+            })
+        );
+        validator.validate_value(&normalized).unwrap();
+
+        let invalid = json!({ "$class": "org.example@1.0.0.Account" });
+        let (unchanged, diagnostics) = validator.normalize(&invalid);
+        assert_eq!(unchanged, invalid);
+        assert!(matches!(diagnostics[..], [Diagnostic { error: ValidationError::MissingRequiredProperty { .. }, .. }]));
+    }
+
+    #[test]
+    fn test_normalize_maps() {
+        let validator = map_validator();
+        let (normalized, diagnostics) = validator.normalize(&registry("schedule", "Schedule", json!({ "2024-01-31T10:00:00+02:00": "standup" })));
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        assert_eq!(
+            normalized["schedule"],
+            json!({ "$class": "org.example@1.0.0.Schedule", "2024-01-31T08:00:00.000Z": "standup" })
+        );
+
+        let same_instant = registry("schedule", "Schedule", json!({ "2024-01-31T10:00:00+02:00": "a", "2024-01-31T08:00:00Z": "b" }));
+        let (unchanged, diagnostics) = validator.normalize(&same_instant);
+        assert_eq!(unchanged, same_instant);
+        assert!(
+            matches!(&diagnostics[..], [Diagnostic { error: ValidationError::InvalidDateTime { reason, .. }, .. }] if reason.contains("2024-01-31T08:00:00.000Z")),
+            "{:?}",
+            diagnostics
+        );
+    }
+}
@@ -3,20 +3,43 @@
 //! A Rust library that validates Accord Project Concerto data models in their JSON AST format
 //! against the [Concerto Metamodel](https://models.accordproject.org/concerto/metamodel@1.0.0.html)
 //! 
-//! Currently, the library only exposes a single function to validate Concerto ASTs,
-//! although internally there are primitive implementations of structures that would sound familiar
+//! The library exposes a convenience function to validate Concerto ASTs with the default
+//! options, and a [`Validator`] that can be configured with [`ValidationOptions`].
+//! [`Validator::diagnose`] also runs lint rules and reports [`Diagnostic`]s with a [`Severity`],
+//! which can be configured per [rule](crate::rules::Rule) with a [`RuleSet`](crate::rules::RuleSet).
+//! Instances of user models can be validated with an [`InstanceValidator`].
+//! Every error has a stable code, e.g. `CV0030`, explained by [`explain`](crate::explain::explain).
+//! Decorator Command Sets can be applied to models with
+//! [`DecoratorCommandSet`](crate::decorator_commands::DecoratorCommandSet).
+//! Internally there are primitive implementations of structures that would sound familiar
 //! to the JS classes, like [`ModelManager`](crate::model_manager::ModelManager). But they are not
 //! ready for public consumption yet.
 
+mod dataset;
+mod datetime;
+pub mod diagnostics;
+pub mod decorator_commands;
 pub mod error;
+pub mod explain;
+mod instances;
 mod model_manager;
+mod numbers;
+mod options;
+mod relationship;
+pub mod rules;
+mod semantic;
 mod validator;
 
 use std::sync::OnceLock;
 
 
+pub use dataset::{DatasetDiagnostic, DatasetValidator};
+pub use diagnostics::{Diagnostic, Severity};
 pub use error::{ValidationError, ValidationResult};
-use validator::Validator;
+pub use instances::InstanceValidator;
+pub use options::InstanceOptions;
+pub use rules::{Rule, RuleSet};
+pub use validator::{ValidationOptions, Validator};
 
 // Reference to hold singleton instance of Validator
 static GLOBAL_VALIDATOR: OnceLock<Option<Validator>> = OnceLock::new();
@@ -138,4 +161,250 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn test_reserved_property_name() {
+        let model = r#"{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "test.namespace@1.0.0",
+            "imports": [],
+            "declarations": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                    "name": "TestConcept",
+                    "isAbstract": false,
+                    "properties": [
+                        {
+                            "$class": "concerto.metamodel@1.0.0.StringProperty",
+                            "name": "$identifier",
+                            "isArray": false,
+                            "isOptional": false
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let result = validate_metamodel(model);
+        assert!(
+            matches!(result, Err(ValidationError::ReservedIdentifier { .. })),
+            "Reserved property names should fail validation: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_invalid_namespace_identifier() {
+        let model = r#"{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "test.1namespace@1.0.0",
+            "imports": [],
+            "declarations": []
+        }"#;
+
+        let result = validate_metamodel(model);
+        assert!(
+            matches!(result, Err(ValidationError::InvalidNamespace { .. })),
+            "Namespaces must be made of identifiers: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_namespace_version_must_be_semver() {
+        let model = r#"{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "test.namespace@latest",
+            "imports": [],
+            "declarations": []
+        }"#;
+
+        let result = validate_metamodel(model);
+        assert!(
+            matches!(result, Err(ValidationError::InvalidNamespaceVersion { .. })),
+            "Namespace versions must be semver: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_require_versioned_namespaces() {
+        let model = r#"{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "test.namespace@1.0.0",
+            "imports": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.ImportAll",
+                    "namespace": "test.other"
+                }
+            ],
+            "declarations": []
+        }"#;
+
+        assert!(validate_metamodel(model).is_ok());
+
+        let validator = Validator::with_options(ValidationOptions::default().require_versioned_namespaces(true)).unwrap();
+        let result = validator.validate(model);
+        assert!(
+            matches!(result, Err(ValidationError::UnversionedNamespace { .. })),
+            "Unversioned imports should fail validation: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_long_domain_validator_bounds() {
+        let model = |upper: &str| format!(r#"{{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "test.namespace@1.0.0",
+            "declarations": [
+                {{
+                    "$class": "concerto.metamodel@1.0.0.LongScalar",
+                    "name": "Amount",
+                    "validator": {{
+                        "$class": "concerto.metamodel@1.0.0.LongDomainValidator",
+                        "lower": 0,
+                        "upper": {}
+                    }}
+                }}
+            ]
+        }}"#, upper);
+
+        let result = validate_metamodel(&model("9223372036854775807"));
+        assert!(result.is_ok(), "Long bounds within i64 should pass: {:?}", result);
+
+        // Numbers are parsed with arbitrary precision, the structural check sees that this is not a Long.
+        let result = validate_metamodel(&model("9223372036854775808"));
+        assert!(
+            matches!(result, Err(ValidationError::InvalidNumber { ref property, .. }) if property == "upper"),
+            "Long bounds outside i64 should fail validation: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_map_key_must_be_string_or_date_time() {
+        let model = r#"{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "test.namespace@1.0.0",
+            "declarations": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                    "name": "Address",
+                    "isAbstract": false,
+                    "properties": []
+                },
+                {
+                    "$class": "concerto.metamodel@1.0.0.MapDeclaration",
+                    "name": "AddressBook",
+                    "key": {
+                        "$class": "concerto.metamodel@1.0.0.ObjectMapKeyType",
+                        "type": {
+                            "$class": "concerto.metamodel@1.0.0.TypeIdentifier",
+                            "name": "Address"
+                        }
+                    },
+                    "value": {
+                        "$class": "concerto.metamodel@1.0.0.StringMapValueType"
+                    }
+                }
+            ]
+        }"#;
+
+        let result = validate_metamodel(model);
+        assert!(
+            matches!(result, Err(ValidationError::InvalidMapDeclaration { ref name, .. }) if name == "AddressBook"),
+            "Concepts cannot be used as map keys: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_asset_cannot_extend_participant() {
+        let model = r#"{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "test.namespace@1.0.0",
+            "declarations": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration",
+                    "name": "Person",
+                    "isAbstract": true,
+                    "properties": []
+                },
+                {
+                    "$class": "concerto.metamodel@1.0.0.AssetDeclaration",
+                    "name": "Car",
+                    "isAbstract": false,
+                    "superType": {
+                        "$class": "concerto.metamodel@1.0.0.TypeIdentifier",
+                        "name": "Person"
+                    },
+                    "properties": []
+                }
+            ]
+        }"#;
+
+        let result = validate_metamodel(model);
+        assert!(
+            matches!(result, Err(ValidationError::IncompatibleSupertype { .. })),
+            "Assets cannot extend participants: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_lint_warnings_do_not_fail_validation() {
+        let model = r#"{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "imports": [],
+            "declarations": [
+                {
+                    "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                    "name": "person",
+                    "isAbstract": false,
+                    "properties": []
+                }
+            ]
+        }"#;
+
+        assert!(validate_metamodel(model).is_ok());
+
+        let diagnostics = Validator::new().unwrap().diagnose(model);
+        assert!(diagnostics.iter().all(|d| !d.is_error()), "{:?}", diagnostics);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Warning && matches!(d.error, ValidationError::NonPascalCaseDeclaration { .. })));
+    }
+
+    #[test]
+    fn test_rule_severities_and_suppression() {
+        let model = |decorators: &str| format!(r#"{{
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "imports": [],
+            "declarations": [
+                {{
+                    "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                    "name": "Empty",
+                    "isAbstract": false,
+                    "decorators": {},
+                    "properties": []
+                }}
+            ]
+        }}"#, decorators);
+        let options = ValidationOptions::default().rules(RuleSet::new().severity(Rule::EmptyConcept, Severity::Error));
+        let validator = Validator::with_options(options).unwrap();
+
+        let result = validator.validate(&model("[]"));
+        assert!(matches!(result, Err(ValidationError::EmptyConcept { .. })), "{:?}", result);
+
+        let suppressed = model(r#"[{
+            "$class": "concerto.metamodel@1.0.0.Decorator",
+            "name": "Suppress",
+            "arguments": [{ "$class": "concerto.metamodel@1.0.0.DecoratorString", "value": "empty-concept" }]
+        }]"#);
+        assert!(validator.validate(&suppressed).is_ok());
+        assert!(validator.diagnose(&suppressed).iter().all(|d| d.rule != Some(Rule::EmptyConcept)));
+    }
 }
@@ -1,5 +1,8 @@
-use clap::{Parser, Subcommand};
-use concerto_validator_rs::{validate_metamodel, ValidationError};
+use clap::{Args, Parser, Subcommand};
+use concerto_validator_rs::explain::explain;
+use concerto_validator_rs::{
+    DatasetValidator, Diagnostic, InstanceOptions, InstanceValidator, RuleSet, ValidationError, ValidationOptions, Validator,
+};
 use std::fs;
 use std::path::PathBuf;
 
@@ -23,7 +26,104 @@ enum Commands {
         /// Stop validation at the first error
         #[arg(long)]
         fail_early: bool,
+
+        /// Require every model and import namespace to carry a version
+        #[arg(long)]
+        require_versioned_namespaces: bool,
+
+        /// Check decorators against the concepts that describe them
+        #[arg(long)]
+        validate_decorators: bool,
+
+        /// Also report lint warnings and informational diagnostics
+        #[arg(long)]
+        lint: bool,
+
+        /// TOML file setting the severity of rules, e.g. `unused-import = "error"`
+        #[arg(long, value_name = "FILE")]
+        rules: Option<PathBuf>,
     },
+    /// Validate instance JSON files against Concerto models
+    ValidateInstance {
+        /// Model JSON ASTs declaring the instance types, imported models first
+        #[arg(short, long, value_name = "FILE", required = true)]
+        model: Vec<PathBuf>,
+
+        /// Instance JSON files to validate (can be specified multiple times)
+        #[arg(short, long, value_name = "FILE")]
+        input: Vec<PathBuf>,
+
+        /// Stop validation at the first error
+        #[arg(long)]
+        fail_early: bool,
+
+        #[command(flatten)]
+        options: InstanceFlags,
+    },
+    /// Validate NDJSON datasets, checking identifiers are unique and relationships point inside each dataset
+    ValidateDataset {
+        /// Model JSON ASTs declaring the instance types, imported models first
+        #[arg(short, long, value_name = "FILE", required = true)]
+        model: Vec<PathBuf>,
+
+        /// NDJSON files with one instance per line, each validated as a dataset
+        #[arg(short, long, value_name = "FILE")]
+        input: Vec<PathBuf>,
+
+        /// Stop validation at the first failing dataset
+        #[arg(long)]
+        fail_early: bool,
+
+        /// Allow relationships to instances outside the dataset, e.g. `org.acme@1.0.0.Driver#D-1` or a whole type
+        #[arg(long, value_name = "REFERENCE")]
+        allow: Vec<String>,
+
+        #[command(flatten)]
+        options: InstanceFlags,
+    },
+    /// Explain an error code, e.g. CV0030
+    Explain {
+        /// The code printed next to the error
+        code: String,
+    },
+}
+
+/// How instances are validated, shared by the instance and dataset commands.
+#[derive(Args)]
+struct InstanceFlags {
+    /// Accept DateTime values without a time, e.g. 2024-01-31
+    #[arg(long)]
+    allow_date_only: bool,
+
+    /// Reject DateTime values with an offset other than UTC
+    #[arg(long)]
+    require_utc: bool,
+
+    /// Reject integral numbers written as decimals, e.g. 1.0, in Integer and Long fields
+    #[arg(long)]
+    strict_integers: bool,
+}
+
+impl From<InstanceFlags> for InstanceOptions {
+    fn from(flags: InstanceFlags) -> Self {
+        InstanceOptions::default()
+            .allow_date_only(flags.allow_date_only)
+            .require_utc(flags.require_utc)
+            .strict_integers(flags.strict_integers)
+    }
+}
+
+/// Why a file failed validation: the error counted in the report,
+/// and details printed below it, e.g. every error of a dataset with its line.
+struct Failure {
+    error: ValidationError,
+    details: Vec<String>,
+}
+
+impl From<ValidationError> for Failure {
+    fn from(error: ValidationError) -> Self {
+        Self { error, details: vec![] }
+    }
 }
 
 #[derive(Debug)]
@@ -64,7 +164,7 @@ impl ValidationReport {
         if !self.errors.is_empty() {
             println!("\nErrors:");
             for (file, error) in &self.errors {
-                println!("  {}: {}", file.display(), error);
+                println!("  {}: [{}] {}", file.display(), error.code(), error);
             }
         }
 
@@ -84,13 +184,46 @@ fn main() {
     let cli = Cli::parse();
 
     let exit_code = match cli.command {
-        Commands::Validate { input, fail_early } => handle_validate_command(input, fail_early),
+        Commands::Validate {
+            input,
+            fail_early,
+            require_versioned_namespaces,
+            validate_decorators,
+            lint,
+            rules,
+        } => match load_rules(rules.as_ref()) {
+            Ok(rules) => {
+                let options = ValidationOptions::default()
+                    .require_versioned_namespaces(require_versioned_namespaces)
+                    .validate_decorators(validate_decorators)
+                    .rules(rules);
+                handle_validate_command(input, fail_early, lint, options)
+            }
+            Err(error) => {
+                eprintln!("Error: {}", error);
+                1
+            }
+        },
+        Commands::ValidateInstance {
+            model,
+            input,
+            fail_early,
+            options,
+        } => handle_validate_instance_command(model, input, fail_early, options.into()),
+        Commands::ValidateDataset {
+            model,
+            input,
+            fail_early,
+            allow,
+            options,
+        } => handle_validate_dataset_command(model, input, fail_early, allow, options.into()),
+        Commands::Explain { code } => handle_explain_command(&code),
     };
 
     std::process::exit(exit_code);
 }
 
-fn handle_validate_command(input_files: Vec<PathBuf>, fail_early: bool) -> i32 {
+fn handle_validate_command(input_files: Vec<PathBuf>, fail_early: bool, lint: bool, options: ValidationOptions) -> i32 {
     if input_files.is_empty() {
         eprintln!(
             "Error: No input files specified. Use --input to specify JSON files to validate."
@@ -98,16 +231,106 @@ fn handle_validate_command(input_files: Vec<PathBuf>, fail_early: bool) -> i32 {
         return 1;
     }
 
+    let validator = match Validator::with_options(options) {
+        Ok(validator) => validator,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            return 1;
+        }
+    };
+
+    run_validations(input_files, fail_early, |file_path| Ok(validate_file(&validator, file_path, lint)?))
+}
+
+fn handle_validate_instance_command(model_files: Vec<PathBuf>, input_files: Vec<PathBuf>, fail_early: bool, options: InstanceOptions) -> i32 {
+    if input_files.is_empty() {
+        eprintln!(
+            "Error: No input files specified. Use --input to specify JSON files to validate."
+        );
+        return 1;
+    }
+
+    let Some(validator) = load_instance_validator(&model_files, options) else {
+        return 1;
+    };
+
+    run_validations(input_files, fail_early, |file_path| {
+        let content = fs::read_to_string(file_path).map_err(ValidationError::IoError)?;
+        validator.validate(&content)?;
+        Ok(vec![])
+    })
+}
+
+fn handle_validate_dataset_command(model_files: Vec<PathBuf>, input_files: Vec<PathBuf>, fail_early: bool, allow: Vec<String>, options: InstanceOptions) -> i32 {
+    if input_files.is_empty() {
+        eprintln!(
+            "Error: No input files specified. Use --input to specify NDJSON files to validate."
+        );
+        return 1;
+    }
+
+    let Some(validator) = load_instance_validator(&model_files, options) else {
+        return 1;
+    };
+    let dataset_validator = allow.iter().fold(DatasetValidator::new(&validator), |dataset_validator, reference| dataset_validator.allow(reference));
+
+    run_validations(input_files, fail_early, |file_path| {
+        let content = fs::read_to_string(file_path).map_err(ValidationError::IoError)?;
+        let diagnostics = dataset_validator.validate_ndjson(&content);
+        let details = diagnostics
+            .iter()
+            .map(|diagnostic| format!("line {}: [{}] {}", diagnostic.line, diagnostic.diagnostic.error.code(), diagnostic.diagnostic.error))
+            .collect();
+        match diagnostics.into_iter().next() {
+            Some(first) => Err(Failure { error: first.diagnostic.error, details }),
+            None => Ok(vec![]),
+        }
+    })
+}
+
+/// Creates an instance validator with the given models, printing the first error.
+fn load_instance_validator(model_files: &[PathBuf], options: InstanceOptions) -> Option<InstanceValidator> {
+    let mut validator = match InstanceValidator::with_options(options) {
+        Ok(validator) => validator,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            return None;
+        }
+    };
+    for model_file in model_files {
+        let added = fs::read_to_string(model_file)
+            .map_err(ValidationError::IoError)
+            .and_then(|content| validator.add_model(&content));
+        if let Err(error) = added {
+            eprintln!("Error: {}: [{}] {}", model_file.display(), error.code(), error);
+            return None;
+        }
+    }
+    Some(validator)
+}
+
+/// Validates each file, printing its outcome, and returns the exit code.
+fn run_validations(
+    input_files: Vec<PathBuf>,
+    fail_early: bool,
+    validate: impl Fn(&PathBuf) -> Result<Vec<Diagnostic>, Failure>,
+) -> i32 {
     let mut report = ValidationReport::new();
 
     for file_path in input_files {
-        match validate_file(&file_path) {
-            Ok(()) => {
+        match validate(&file_path) {
+            Ok(diagnostics) => {
                 println!("✅ {}: Valid", file_path.display());
+                for diagnostic in diagnostics {
+                    println!("   {}", diagnostic);
+                }
                 report.add_success();
             }
-            Err(error) => {
-                println!("❌ {}: {}", file_path.display(), error);
+            Err(Failure { error, details }) => {
+                println!("❌ {}: [{}] {}", file_path.display(), error.code(), error);
+                for detail in details {
+                    println!("   {}", detail);
+                }
                 report.add_error(file_path, error);
 
                 if fail_early {
@@ -129,10 +352,41 @@ fn handle_validate_command(input_files: Vec<PathBuf>, fail_early: bool) -> i32 {
     }
 }
 
-fn validate_file(file_path: &PathBuf) -> Result<(), ValidationError> {
+fn handle_explain_command(code: &str) -> i32 {
+    match explain(code) {
+        Some(explanation) => {
+            println!("{}", explanation);
+            0
+        }
+        None => {
+            eprintln!("Error: Unknown error code {}", code);
+            1
+        }
+    }
+}
+
+fn load_rules(file_path: Option<&PathBuf>) -> Result<RuleSet, ValidationError> {
+    match file_path {
+        Some(file_path) => RuleSet::from_toml(&fs::read_to_string(file_path)?),
+        None => Ok(RuleSet::default()),
+    }
+}
+
+/// Validates a file and, when linting, returns its warnings and informational diagnostics.
+fn validate_file(validator: &Validator, file_path: &PathBuf, lint: bool) -> Result<Vec<Diagnostic>, ValidationError> {
     // Read the file
     let content = fs::read_to_string(file_path).map_err(ValidationError::IoError)?;
 
     // Validate the content
-    validate_metamodel(&content)
+    if !lint {
+        return validator.validate(&content).map(|()| vec![]);
+    }
+    let (errors, diagnostics): (Vec<_>, Vec<_>) = validator
+        .diagnose(&content)
+        .into_iter()
+        .partition(Diagnostic::is_error);
+    match errors.into_iter().next() {
+        Some(error) => Err(error.error),
+        None => Ok(diagnostics),
+    }
 }
@@ -5,33 +5,73 @@
 //! implementation.
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
 
 /// A serialization of Concerto `Property` definition from AST.
+/// `EnumProperty` has neither `isArray` nor `isOptional`.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct Property {
     #[serde(rename = "$class")]
     pub class: String,
     pub name: String,
-    #[serde(rename = "isArray")]
+    #[serde(rename = "isArray", default)]
     pub is_array: bool,
-    #[serde(rename = "isOptional")]
+    #[serde(rename = "isOptional", default)]
     pub is_optional: bool,
     #[serde(rename = "type")]
     pub super_type: Option<SuperType>,
     pub validator: Option<Validator>,
+    #[serde(rename = "lengthValidator")]
+    pub length_validator: Option<LengthValidator>,
+    #[serde(rename = "defaultValue")]
+    pub default_value: Option<Value>,
 }
 
 /// A serialization of Concerto `ConceptDeclaration` definition from AST.
+/// Other declarations, such as scalars and enums, are read into the
+/// same structure, with the fields they do not have left empty.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct ConceptDeclaration {
     #[serde(rename = "$class")]
     pub class: String,
-    #[serde(rename = "isAbstract")]
-    is_abstract: bool,
+    #[serde(rename = "isAbstract", default)]
+    pub is_abstract: bool,
+    #[serde(default)]
     pub properties: Vec<Property>,
     pub name: String,
     #[serde(rename = "superType")]
     pub super_type: Option<SuperType>,
+    /// The validators of a scalar declaration.
+    pub validator: Option<Validator>,
+    #[serde(rename = "lengthValidator")]
+    pub length_validator: Option<LengthValidator>,
+    /// The default value of a scalar declaration.
+    #[serde(rename = "defaultValue")]
+    pub default_value: Option<Value>,
+    /// `Identified` or `IdentifiedBy` for types with an identity.
+    pub identified: Option<Identified>,
+    /// The key and value types of a map declaration.
+    pub key: Option<MapEntryType>,
+    pub value: Option<MapEntryType>,
+}
+
+/// A serialization of `Identified` and `IdentifiedBy` from AST.
+/// Types identified by a field have its name, the others use `$identifier`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct Identified {
+    #[serde(rename = "$class")]
+    pub class: String,
+    pub name: Option<String>,
+}
+
+/// A serialization of the key and value types of a `MapDeclaration` from AST,
+/// e.g. `StringMapKeyType` or `ObjectMapValueType`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct MapEntryType {
+    #[serde(rename = "$class")]
+    pub class: String,
+    #[serde(rename = "type")]
+    pub super_type: Option<SuperType>,
 }
 
 /// A serialization of parent type references from AST.
@@ -44,10 +84,32 @@ pub(crate) struct SuperType {
 }
 
 /// A serialization of validator definition from AST.
+/// Only `StringRegexValidator` has a pattern, domain validators have bounds.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct Validator {
     #[serde(rename = "$class")]
     pub class: String,
-    pub pattern: String,
+    pub pattern: Option<String>,
+    #[serde(default)]
     pub flags: String,
-}
\ No newline at end of file
+    pub lower: Option<Number>,
+    pub upper: Option<Number>,
+}
+
+/// A serialization of `StringLengthValidator` definition from AST.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct LengthValidator {
+    #[serde(rename = "$class")]
+    pub class: String,
+    #[serde(rename = "minLength")]
+    pub min_length: Option<i64>,
+    #[serde(rename = "maxLength")]
+    pub max_length: Option<i64>,
+}
+
+impl Validator {
+    /// Key of the compiled pattern in the regex cache, e.g. `/^[a-z]+$/i`.
+    pub fn regex_key(&self) -> Option<String> {
+        self.pattern.as_ref().map(|pattern| format!("/{}/{}", pattern, self.flags))
+    }
+}
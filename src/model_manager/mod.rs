@@ -3,30 +3,48 @@ mod type_definition;
 mod ast_structures;
 
 use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 
-use serde_json::{Map, Value};
-use regex::Regex;
+use serde_json::{Map, Number, Value};
+use fancy_regex::Regex;
 
+use crate::datetime::DateTime;
 use crate::error::ValidationError;
-use crate::model_manager::ast_structures::{ConceptDeclaration, Property, SuperType};
+use crate::model_manager::ast_structures::{ConceptDeclaration, Identified, MapEntryType, Property, SuperType};
 use crate::model_manager::type_definition::TypeDefinition;
-
-type JsonObject = Map<String, Value>;
+use crate::numbers::{as_integral, Integral};
+use crate::options::InstanceOptions;
+use crate::relationship::{Identity, Reference, RelationshipUri};
+use crate::semantic::model_file::{objects, resolve_import};
+use crate::semantic::scalars::parse_default_value;
+use crate::semantic::validators::{check_length, check_value_in_range, compile_pattern, matches_pattern};
+
+pub(crate) type JsonObject = Map<String, Value>;
 type TypeRegistry = HashMap<String, TypeDefinition>;
 
-const CONCERTO_METAMODEL_NAMESPACE: &str = "concerto.metamodel@1.0.0";
+pub(crate) const CONCERTO_METAMODEL_NAMESPACE: &str = "concerto.metamodel@1.0.0";
+
+/// The properties of a class, including the inherited ones.
+struct HierarchyProperties<'a> {
+    expected: HashMap<String, &'a Property>,
+    required: HashMap<String, &'a Property>,
+    identified: Option<&'a Identified>,
+}
 
 /// Loads the system definitions and validates
 /// given resource.
-/// Currently, there isn't a way to add more metamodels.
+/// More models can be registered with [`add_model`](ModelManager::add_model),
+/// to validate instances of their types.
 pub(crate) struct ModelManager {
     /// Internal look up for all the loaded type definitions.
     /// See [`TypeDefinition`](crate::model_manager::type_definition::TypeDefinition).
     type_registry: TypeRegistry,
     /// Internal look up for string validator regexes.
     /// Regexes are pre-compiled at creation time.
-    /// See [`Regex`](regex::Regex).
+    /// See [`Regex`](fancy_regex::Regex).
     regex_cache: HashMap<String, Regex>,
+    /// Policies for the values of instances of user models.
+    instance_options: InstanceOptions,
 }
 
 /// Public API
@@ -37,14 +55,71 @@ impl<'model_manager> ModelManager {
         let metamodel_json = include_str!("../../metamodel.json");
         let concerto_metamodel: Value = serde_json::from_str(metamodel_json)?;
 
-        let type_registry = Self::build_type_registry(&concerto_metamodel)?;
-        let regex_cache = Self::build_regex_cache(&type_registry);
+        let mut model_manager = Self {
+            type_registry: TypeRegistry::new(),
+            regex_cache: HashMap::new(),
+            instance_options: InstanceOptions::default(),
+        };
+        model_manager.add_model(&concerto_metamodel)?;
+        Ok(model_manager)
+    }
+
+    /// Registers the declarations of a `Model`, or of all the models of a `Models` AST,
+    /// so that their instances can be validated as well.
+    /// Type names are qualified with their namespace following the imports,
+    /// so imported types must be registered before, or in the same `Models` AST,
+    /// otherwise nothing is registered.
+    pub fn add_model(&mut self, model: &'model_manager Value) -> Result<(), ValidationError> {
+        let models = match model.get("models").and_then(Value::as_array) {
+            Some(models) => models.iter().collect::<Vec<_>>(),
+            None => vec![model],
+        };
+
+        let mut type_registry = TypeRegistry::new();
+        for model in &models {
+            type_registry.extend(Self::build_type_registry(model)?);
+        }
+
+        let known_types = type_registry.keys().chain(self.type_registry.keys()).cloned().collect::<HashSet<_>>();
+        for model in &models {
+            let namespace = model.get("namespace").and_then(Value::as_str).unwrap_or_default();
+            type_registry
+                .values_mut()
+                .filter(|type_def| type_def.namespace == namespace)
+                .for_each(|type_def| Self::qualify_type_names(type_def, model, &known_types));
+        }
+        for type_def in type_registry.values() {
+            let type_identifiers = type_def
+                .inner
+                .properties
+                .iter()
+                .filter_map(|property| property.super_type.as_ref())
+                .chain([&type_def.inner.key, &type_def.inner.value].into_iter().flatten().filter_map(|entry| entry.super_type.as_ref()))
+                .chain(type_def.get_supertype());
+            for type_identifier in type_identifiers {
+                let name = Self::qualified_name(type_identifier)?;
+                if !known_types.contains(&name) {
+                    return Err(ValidationError::MissingTypeDefinition { name });
+                }
+            }
+        }
 
-        Ok(Self { type_registry, regex_cache })
+        self.regex_cache.extend(Self::build_regex_cache(&type_registry));
+        self.type_registry.extend(type_registry);
+        Ok(())
+    }
+
+    pub fn set_instance_options(&mut self, instance_options: InstanceOptions) {
+        self.instance_options = instance_options;
     }
 
     /// Validate a Concerto AST.
     pub fn validate_metamodel(&self, thing: &'model_manager Value) -> Result<(), ValidationError> {
+        self.validate_instance(thing)
+    }
+
+    /// Validate an instance of any of the loaded types.
+    pub fn validate_instance(&self, thing: &'model_manager Value) -> Result<(), ValidationError> {
         let obj = self.get_serialized_object(thing)?;
         self.validate_resource(obj)
     }
@@ -57,32 +132,136 @@ impl<'model_manager> ModelManager {
         let class_name = self.get_class_name(thing)?;
 
         let type_def = self.get_type_definition(class_name)?;
+        if !type_def.is_class() {
+            return Err(ValidationError::TypeMismatch {
+                expected: "a concept, asset, participant, transaction or event".to_string(),
+                found: class_name.to_string(),
+            });
+        }
+        if type_def.inner.is_abstract {
+            return Err(ValidationError::AbstractInstance {
+                class_name: class_name.to_string(),
+            });
+        }
+
+        let hierarchy = self.get_hierarchy_properties(type_def)?;
+        self.validate_system_properties(thing, type_def, hierarchy.identified)?;
+        self.validate_expected_properties(thing, &hierarchy.expected)?;
+        self.validate_required_properties(thing, &hierarchy.required)?;
+        self.validate_property_structure(thing, &hierarchy.expected)?;
+        Ok(())
+    }
 
-        let mut expected_properties = type_def.expected_properties();
-        let mut required_properties = type_def.required_properties();
+    /// Collects the properties of a class and of its supertypes, and its identity,
+    /// which can be declared by a supertype.
+    fn get_hierarchy_properties<'a>(&'a self, type_def: &'a TypeDefinition) -> Result<HierarchyProperties<'a>, ValidationError> {
+        let mut expected = type_def.expected_properties();
+        let mut required = type_def.required_properties();
+        let mut identified = type_def.inner.identified.as_ref();
 
-        if type_def.has_supertype() {
-            let super_type = type_def.get_supertype().ok_or(ValidationError::MissingSuperTypeDefinition {
-                name: class_name.to_string(),
-            })?;
-            let super_type_definition = self.get_supertype_definition(super_type)?;
+        // Walk up the whole hierarchy, e.g. `LongScalar` -> `ScalarDeclaration` -> `Declaration`.
+        for super_type_definition in self.get_supertype_definitions(type_def)? {
             super_type_definition.expected_properties().iter().for_each(|(k, v)| {
-               expected_properties.insert(k.to_string(), v);
+               expected.insert(k.to_string(), v);
             });
             super_type_definition.required_properties().iter().for_each(|(k, v)| {
-                required_properties.insert(k.to_string(), v);
+                required.insert(k.to_string(), v);
             });
+            identified = identified.or(super_type_definition.inner.identified.as_ref());
+        }
+        Ok(HierarchyProperties { expected, required, identified })
+    }
+
+    /// The ancestors of a type, from its supertype up. Models registered without the semantic
+    /// checks can contain cycles, which are reported instead of being walked forever.
+    fn get_supertype_definitions<'a>(&'a self, type_def: &'a TypeDefinition) -> Result<Vec<&'a TypeDefinition>, ValidationError> {
+        let mut ancestors: Vec<&TypeDefinition> = vec![];
+        let mut current_definition = type_def;
+        while current_definition.has_supertype() {
+            current_definition = self.get_supertype_definition(current_definition)?;
+            if std::ptr::eq(current_definition, type_def) || ancestors.iter().any(|ancestor| std::ptr::eq(*ancestor, current_definition)) {
+                let cycle = std::iter::once(type_def)
+                    .chain(ancestors)
+                    .chain(std::iter::once(current_definition))
+                    .map(TypeDefinition::full_name)
+                    .collect::<Vec<_>>();
+                return Err(ValidationError::CyclicSupertype {
+                    declaration: type_def.full_name(),
+                    cycle: cycle.join(" -> "),
+                });
+            }
+            ancestors.push(current_definition);
+        }
+        Ok(ancestors)
+    }
+
+    /// System properties start with `$`: besides `$class`, instances of identified types
+    /// carry `$identifier`, and transactions and events a `$timestamp`. Types identified
+    /// by a field only need the field, `$identifier` must match it when present.
+    fn validate_system_properties(&self, thing: &'model_manager JsonObject, type_def: &TypeDefinition, identified: Option<&Identified>) -> Result<(), ValidationError> {
+        let invalid = |property: &str, reason: String| ValidationError::InvalidSystemProperty {
+            property: property.to_string(),
+            class_name: type_def.full_name(),
+            reason,
+        };
+
+        for key in thing.keys().filter(|key| key.starts_with('$')) {
+            match key.as_str() {
+                "$class" => {}
+                "$identifier" if identified.is_some() => {}
+                "$timestamp" if type_def.has_timestamp() => {}
+                "$identifier" => return Err(invalid(key, "the type is not identified".to_string())),
+                "$timestamp" => return Err(invalid(key, "only transactions and events have a timestamp".to_string())),
+                _ => return Err(invalid(key, "unknown system property".to_string())),
+            }
+        }
+
+        if let Some(identified) = identified {
+            match (thing.get("$identifier"), &identified.name) {
+                (None, None) => {
+                    return Err(ValidationError::MissingRequiredProperty {
+                        property: "$identifier".to_string(),
+                    })
+                }
+                (None, Some(_)) => {}
+                (Some(identifier), field) => {
+                    let identifier = identifier
+                        .as_str()
+                        .ok_or_else(|| invalid("$identifier", format!("expected a String, found {}", identifier)))?;
+                    if identifier.is_empty() {
+                        return Err(invalid("$identifier", "the identifier is empty".to_string()));
+                    }
+                    if let Some((field, value)) = field.as_ref().and_then(|field| thing.get(field).map(|value| (field, value))) {
+                        if value.as_str() != Some(identifier) {
+                            return Err(invalid("$identifier", format!("'{}' does not match {} {}", identifier, field, value)));
+                        }
+                    }
+                }
+            }
         }
 
-        self.validate_expected_properties(thing, &expected_properties)?;
-        self.validate_required_properties(thing, &required_properties)?;
-        self.validate_property_structure(thing, &expected_properties)?;
+        if type_def.has_timestamp() {
+            let timestamp = thing.get("$timestamp").ok_or_else(|| ValidationError::MissingRequiredProperty {
+                property: "$timestamp".to_string(),
+            })?;
+            let timestamp_property = Property {
+                class: format!("{}.DateTimeProperty", CONCERTO_METAMODEL_NAMESPACE),
+                name: "$timestamp".to_string(),
+                is_array: false,
+                is_optional: false,
+                super_type: None,
+                validator: None,
+                length_validator: None,
+                default_value: None,
+            };
+            self.validate_property(&timestamp_property, timestamp)?;
+        }
         Ok(())
     }
 
     fn validate_expected_properties(&self, thing: &'model_manager JsonObject, expected_properties: &HashMap<String, &Property>) -> Result<(), ValidationError> {
         let invalid_properties = thing
-            .keys().filter(|&x| !expected_properties.contains_key(x) && x != "$class").cloned()
+            .keys().filter(|&x| !expected_properties.contains_key(x) && !x.starts_with('$')).cloned()
             .collect::<Vec<String>>();
 
         if !invalid_properties.is_empty() {
@@ -112,62 +291,24 @@ impl<'model_manager> ModelManager {
     }
 
     fn validate_property_structure(&self, thing: &'model_manager JsonObject, properties: &HashMap<String, &Property>) -> Result<(), ValidationError> {
-        let validations = thing
-            .iter()
-            .map(|(prop_name, prop_value)| {
-                if prop_name == "$class" {
-                    return Ok(());
-                }
-                if let Some(property_type) = properties.get(prop_name) {
-                    if property_type.is_array {
-                        if prop_value.is_array() {
-                            let validation_errors = prop_value.as_array().unwrap()
-                                .iter()
-                                .map(|x| {
-                                    self.validate_property(property_type, x)
-                                })
-                                .filter(|x| x.is_err())
-                                .collect::<Vec<Result<(), ValidationError>>>();
-
-                            if !validation_errors.is_empty() {
-                                match validation_errors.first().unwrap() {
-                                    Err(e) => Err(ValidationError::Generic {
-                                        message: e.to_string(),
-                                    }),
-                                    _ => unreachable!()
-                                }
-                            } else {
-                                Ok(())
-                            }
-                        } else {
-                            Err(ValidationError::Generic {
-                                message: format!("Error validating property {:}. Expected an array.", prop_name),
-                            })
-                        }
-
-                    } else {
-                        self.validate_property(property_type, prop_value)
-                    }
-                } else {
-                    Err(ValidationError::Generic {
-                        message: format!("Error validating property {:}", prop_name),
-                    })
+        for (prop_name, prop_value) in thing {
+            if prop_name.starts_with('$') {
+                continue;
+            }
+            let property_type = properties.get(prop_name).ok_or_else(|| ValidationError::Generic {
+                message: format!("Error validating property {:}", prop_name),
+            })?;
+            if property_type.is_array {
+                let values = prop_value.as_array().ok_or_else(|| ValidationError::Generic {
+                    message: format!("Error validating property {:}. Expected an array.", prop_name),
+                })?;
+                for value in values {
+                    self.validate_property(property_type, value)?;
                 }
-            })
-            .collect::<Vec<_>>();
-
-        let validation_errors = validations
-            .iter()
-            .filter(|x| x.is_err())
-            .collect::<Vec<_>>();
-
-        if !validation_errors.is_empty() {
-            let first_err = validation_errors.first();
-            return Err(ValidationError::Generic {
-                message: format!("{:?}", first_err),
-            });
-        };
-
+            } else {
+                self.validate_property(property_type, prop_value)?;
+            }
+        }
         Ok(())
     }
 }
@@ -181,12 +322,15 @@ impl<'model_manager> ModelManager {
     ) -> Result<(), ValidationError> {
         match type_def.class.as_str() {
             "concerto.metamodel@1.0.0.ObjectProperty" => {
-                self.validate_object_property(thing)
+                self.validate_object_property(thing, type_def)
             },
             "concerto.metamodel@1.0.0.StringProperty" => self.validate_string_property(thing, type_def),
             "concerto.metamodel@1.0.0.BooleanProperty" => self.validate_boolean_property(thing),
-            "concerto.metamodel@1.0.0.DoubleProperty" => self.validate_double_property(thing),
-            "concerto.metamodel@1.0.0.IntegerProperty" => self.validate_integer_property(thing),
+            "concerto.metamodel@1.0.0.DoubleProperty" => self.validate_double_property(thing, type_def),
+            "concerto.metamodel@1.0.0.IntegerProperty" => self.validate_integral_property(thing, type_def, 32, "Integer"),
+            "concerto.metamodel@1.0.0.LongProperty" => self.validate_integral_property(thing, type_def, 64, "Long"),
+            "concerto.metamodel@1.0.0.DateTimeProperty" => self.validate_date_time_property(thing, type_def),
+            "concerto.metamodel@1.0.0.RelationshipProperty" => self.validate_relationship_property(thing, type_def),
             _ => Err(ValidationError::ValidationFailed {
                 message: "Unknown property type".to_string(),
             }),
@@ -197,17 +341,21 @@ impl<'model_manager> ModelManager {
         let str = thing.as_str().ok_or(ValidationError::UnexpectedType {
             expected: "String".to_string(),
         })?;
-        if let Some(validator) = &type_def.validator {
-            let pattern = &validator.pattern;
-            let re = self.regex_cache.get(pattern).ok_or( ValidationError::StringValidationError {
+        if let Some(pattern) = type_def.validator.as_ref().and_then(|validator| validator.regex_key()) {
+            let re = self.regex_cache.get(&pattern).ok_or( ValidationError::StringValidationError {
                 message: format!("Cannot compile pattern {}", pattern)
             })?;
-            if !re.is_match(str) {
+            let matches = matches_pattern(re, str).map_err(|message| ValidationError::StringValidationError { message })?;
+            if !matches {
                 return Err(ValidationError::StringValidationError {
                     message: format!("Invalid string property: {}", str)
                 })
             }
         }
+        if let Some(length_validator) = &type_def.length_validator {
+            check_length(str, length_validator.min_length, length_validator.max_length)
+                .map_err(|reason| Self::validator_violation(type_def, thing, reason))?;
+        }
         Ok(())
     }
 
@@ -219,25 +367,455 @@ impl<'model_manager> ModelManager {
             }).map(|_| ())
     }
 
-    fn validate_integer_property(&self, thing: &'model_manager Value) -> Result<(), ValidationError> {
-        thing
-            .as_i64()
-            .ok_or(ValidationError::UnexpectedType {
-                expected: "Integer".to_string(),
-            }).map(|_| ())
+    /// `Integer` values are 32-bit and `Long` values 64-bit. Integral numbers written
+    /// as decimals, e.g. `1.0`, are rejected if [`InstanceOptions::strict_integers`] is set.
+    fn validate_integral_property(&self, thing: &'model_manager Value, type_def: &Property, bits: u32, type_name: &str) -> Result<(), ValidationError> {
+        let number = thing.as_number().ok_or(ValidationError::UnexpectedType {
+            expected: type_name.to_string(),
+        })?;
+        let invalid = |reason: String| ValidationError::InvalidNumber {
+            property: type_def.name.clone(),
+            value: number.to_string(),
+            reason,
+        };
+
+        let integral = Integral::parse(number).map_err(invalid)?;
+        if integral.decimal && self.instance_options.strict_integers {
+            return Err(invalid(format!("expected an {} written without a fraction or exponent, e.g. {}", type_name, integral.value)));
+        }
+        let value = integral.check_bits(bits, type_name).map_err(invalid)?;
+        Self::validate_domain(type_def, thing, value, as_integral)
+    }
+
+    /// `DateTime` values are ISO-8601 date-times with an offset, e.g. `2024-01-31T10:00:00Z`,
+    /// or dates if [`InstanceOptions::allow_date_only`] is set.
+    fn validate_date_time_property(&self, thing: &'model_manager Value, type_def: &Property) -> Result<(), ValidationError> {
+        let value = thing.as_str().ok_or(ValidationError::UnexpectedType {
+            expected: "DateTime".to_string(),
+        })?;
+        let invalid = |reason: String| ValidationError::InvalidDateTime {
+            property: type_def.name.clone(),
+            value: value.to_string(),
+            reason,
+        };
+
+        let date_time = DateTime::parse(value).map_err(invalid)?;
+        date_time.check(&self.instance_options).map_err(invalid)
+    }
+
+    /// Relationships are URIs such as `resource:org.acme@1.0.0.Driver#D-123`, or bare identifiers.
+    /// The type in the URI must be the declared type or one of its subtypes.
+    fn validate_relationship_property(&self, thing: &'model_manager Value, type_def: &Property) -> Result<(), ValidationError> {
+        let value = thing.as_str().ok_or(ValidationError::UnexpectedType {
+            expected: "Relationship".to_string(),
+        })?;
+        let invalid = |reason: String| ValidationError::InvalidRelationship {
+            property: type_def.name.clone(),
+            value: value.to_string(),
+            reason,
+        };
+
+        let uri = RelationshipUri::parse(value).map_err(invalid)?;
+        let (Some(target), Some(type_identifier)) = (uri.type_name.as_deref(), &type_def.super_type) else {
+            return Ok(());
+        };
+        let expected = Self::qualified_name(type_identifier)?;
+        let target = self.resolve_relationship_type(&uri, Some(&expected)).map_err(|reason| invalid(reason.unwrap_or_else(|| format!("type {} is not declared", target))))?;
+        if !self.is_assignable(&target, &expected) {
+            return Err(invalid(format!("{} is not a {}", target, expected)));
+        }
+        Ok(())
+    }
+
+    /// Resolves the type of a relationship URI. Without a version in the URI,
+    /// the namespace matches any registered version, e.g. `org.acme` matches `org.acme@1.0.0`.
+    /// When several versions match, the version of the declared type `expected` is preferred,
+    /// otherwise the URI is ambiguous and the error carries the reason.
+    fn resolve_relationship_type(&self, uri: &RelationshipUri, expected: Option<&str>) -> Result<String, Option<String>> {
+        let type_name = uri.type_name.as_deref().ok_or(None)?;
+        if self.type_registry.contains_key(type_name) {
+            return Ok(type_name.to_string());
+        }
+        let (namespace, name) = (uri.namespace().ok_or(None)?, uri.name().ok_or(None)?);
+        if namespace.contains('@') {
+            return Err(None);
+        }
+        let mut candidates: Vec<&TypeDefinition> = self
+            .type_registry
+            .values()
+            .filter(|type_def| type_def.inner.name == name && type_def.namespace.split('@').next() == Some(namespace))
+            .collect();
+        let expected_namespace = expected.and_then(|expected| expected.rsplit_once('.')).map(|(namespace, _)| namespace);
+        if let Some(type_def) = candidates.iter().find(|type_def| Some(type_def.namespace.as_str()) == expected_namespace) {
+            return Ok(type_def.full_name());
+        }
+        match candidates.as_slice() {
+            [] => Err(None),
+            [type_def] => Ok(type_def.full_name()),
+            _ => {
+                candidates.sort_by(|a, b| a.namespace.cmp(&b.namespace));
+                let names: Vec<String> = candidates.iter().map(|type_def| type_def.full_name()).collect();
+                Err(Some(format!("type {} is ambiguous, it matches {}", type_name, names.join(", "))))
+            }
+        }
+    }
+
+    fn validate_double_property(&self, thing: &'model_manager Value, type_def: &Property) -> Result<(), ValidationError> {
+        let number = thing.as_number().ok_or(ValidationError::UnexpectedType {
+            expected: "Double".to_string(),
+        })?;
+        let value = number.as_f64().ok_or_else(|| ValidationError::InvalidNumber {
+            property: type_def.name.clone(),
+            value: number.to_string(),
+            reason: "outside the range of a 64-bit Double".to_string(),
+        })?;
+        Self::validate_domain(type_def, thing, value, Number::as_f64)
+    }
+
+    /// Maps are objects with the `$class` of the map declaration, whose other members are the entries.
+    /// Keys are `String` or `DateTime` values, possibly of a scalar type, and values are checked
+    /// like properties of the value type, e.g. `ObjectMapValueType` like an `ObjectProperty`.
+    fn validate_map(&self, thing: &'model_manager Value, type_def: &Property, map_def: &TypeDefinition) -> Result<(), ValidationError> {
+        let obj = self.get_serialized_object(thing)?;
+        let class_name = self.get_class_name(obj)?;
+        let map_name = map_def.full_name();
+        if class_name != map_name {
+            return Err(ValidationError::TypeMismatch {
+                expected: map_name,
+                found: class_name.to_string(),
+            });
+        }
+        let (Some(key_type), Some(value_type)) = (&map_def.inner.key, &map_def.inner.value) else {
+            return Err(ValidationError::ValidationFailed {
+                message: format!("Map {} has no key or value type", map_name),
+            });
+        };
+
+        let key_property = Self::map_entry_property(key_type, "MapKeyType", format!("{} key", type_def.name));
+        for (key, value) in obj.iter().filter(|(key, _)| key.as_str() != "$class") {
+            self.validate_property(&key_property, &Value::String(key.clone()))?;
+            let value_property = Self::map_entry_property(value_type, "MapValueType", format!("{}[{}]", type_def.name, key));
+            self.validate_property(&value_property, value)?;
+        }
+        Ok(())
+    }
+
+    /// The property standing for the keys or the values of a map, e.g. a `StringProperty` for `StringMapKeyType`.
+    fn map_entry_property(entry_type: &MapEntryType, suffix: &str, name: String) -> Property {
+        let kind = entry_type.class.rsplit('.').next().unwrap_or_default();
+        Property {
+            class: format!("{}.{}Property", CONCERTO_METAMODEL_NAMESPACE, kind.strip_suffix(suffix).unwrap_or(kind)),
+            name,
+            is_array: false,
+            is_optional: false,
+            super_type: entry_type.super_type.clone(),
+            validator: None,
+            length_validator: None,
+            default_value: None,
+        }
     }
 
-    fn validate_double_property(&self, thing: &'model_manager Value) -> Result<(), ValidationError> {
+    /// Enum values are serialized as the name of the value, e.g. `"RED"`.
+    fn validate_enum_value(thing: &Value, property_name: &str, enum_def: &TypeDefinition) -> Result<(), ValidationError> {
+        let values = &enum_def.inner.properties;
+        if thing.as_str().is_some_and(|value| values.iter().any(|enum_value| enum_value.name == value)) {
+            return Ok(());
+        }
+        Err(ValidationError::InvalidEnumValue {
+            property: property_name.to_string(),
+            value: thing.to_string(),
+            allowed: values.iter().map(|enum_value| enum_value.name.as_str()).collect::<Vec<_>>().join(", "),
+        })
+    }
+
+    /// Checks a number against the `lower` and `upper` bounds of the domain validator of a property.
+    fn validate_domain<T: PartialOrd + Display>(
+        type_def: &Property,
+        thing: &Value,
+        value: T,
+        bound: fn(&Number) -> Option<T>,
+    ) -> Result<(), ValidationError> {
+        let Some(validator) = &type_def.validator else {
+            return Ok(());
+        };
+        let lower = validator.lower.as_ref().and_then(bound);
+        let upper = validator.upper.as_ref().and_then(bound);
+        check_value_in_range(value, lower, upper).map_err(|reason| Self::validator_violation(type_def, thing, reason))
+    }
+
+    fn validator_violation(type_def: &Property, thing: &Value, reason: String) -> ValidationError {
+        ValidationError::ValidatorViolation {
+            property: type_def.name.clone(),
+            value: thing.to_string(),
+            reason,
+        }
+    }
+
+    /// Objects must be instances of the property type or one of its subtypes.
+    /// Properties typed with a scalar hold a value of the scalar primitive type,
+    /// properties typed with an enum the name of one of its values, and properties typed with a map an object.
+    fn validate_object_property(&self, thing: &'model_manager Value, type_def: &Property) -> Result<(), ValidationError> {
+        let Some(type_identifier) = &type_def.super_type else {
+            let obj = self.get_serialized_object(thing)?;
+            return self.validate_resource(obj);
+        };
+        let target = self.get_type_definition(&Self::qualified_name(type_identifier)?)?;
+
+        if target.is_class() {
+            let obj = self.get_serialized_object(thing)?;
+            let class_name = self.get_class_name(obj)?;
+            let target_name = target.full_name();
+            if !self.is_assignable(class_name, &target_name) {
+                return Err(ValidationError::TypeMismatch {
+                    expected: target_name,
+                    found: class_name.to_string(),
+                });
+            }
+            return self.validate_resource(obj);
+        }
+
+        match target.kind() {
+            "EnumDeclaration" => return Self::validate_enum_value(thing, &type_def.name, target),
+            "MapDeclaration" => return self.validate_map(thing, type_def, target),
+            _ => {}
+        }
+
+        match target.kind().strip_suffix("Scalar") {
+            Some(primitive) => {
+                let scalar_property = Property {
+                    class: format!("{}.{}Property", CONCERTO_METAMODEL_NAMESPACE, primitive),
+                    name: type_def.name.clone(),
+                    is_array: false,
+                    is_optional: false,
+                    super_type: None,
+                    validator: target.inner.validator.clone(),
+                    length_validator: target.inner.length_validator.clone(),
+                    default_value: None,
+                };
+                self.validate_property(&scalar_property, thing)
+            }
+            None => Err(ValidationError::ValidationFailed {
+                message: format!("Unsupported type {} of property {}", target.kind(), type_def.name),
+            }),
+        }
+    }
+}
+
+/// Functions related to the normalization of valid instances.
+impl<'model_manager> ModelManager {
+    /// Validates an instance and returns its canonical form: missing properties with a default value
+    /// are filled in, `$identifier` is copied from the identifying field and `DateTime` values are in UTC.
+    pub fn normalize_instance(&self, thing: &'model_manager Value) -> Result<Value, ValidationError> {
+        self.validate_instance(thing)?;
+        self.normalize_resource(self.get_serialized_object(thing)?).map(Value::Object)
+    }
+
+    fn normalize_resource(&self, thing: &'model_manager JsonObject) -> Result<JsonObject, ValidationError> {
+        let type_def = self.get_type_definition(self.get_class_name(thing)?)?;
+        let hierarchy = self.get_hierarchy_properties(type_def)?;
+
+        let mut normalized = JsonObject::new();
+        for (name, value) in thing {
+            let value = match (hierarchy.expected.get(name), value.as_array()) {
+                (Some(property), Some(values)) if property.is_array => {
+                    Value::Array(values.iter().map(|value| self.normalize_value(property, value)).collect::<Result<_, _>>()?)
+                }
+                (Some(property), _) => self.normalize_value(property, value)?,
+                (None, _) if name == "$timestamp" => Self::normalize_date_time(value),
+                (None, _) => value.clone(),
+            };
+            normalized.insert(name.clone(), value);
+        }
+
+        for (name, property) in &hierarchy.expected {
+            if normalized.contains_key(name) || property.is_array {
+                continue;
+            }
+            if let Some(default_value) = self.get_default_value(property)? {
+                normalized.insert(name.clone(), self.normalize_value(property, &default_value)?);
+            }
+        }
+
+        let identifying_field = hierarchy.identified.and_then(|identified| identified.name.as_ref());
+        if let Some(identifier) = identifying_field.and_then(|field| normalized.get(field)).cloned() {
+            normalized.insert("$identifier".to_string(), identifier);
+        }
+        Ok(normalized)
+    }
+
+    fn normalize_value(&self, type_def: &Property, thing: &'model_manager Value) -> Result<Value, ValidationError> {
+        match type_def.class.strip_prefix(CONCERTO_METAMODEL_NAMESPACE).unwrap_or_default() {
+            ".DateTimeProperty" => Ok(Self::normalize_date_time(thing)),
+            ".ObjectProperty" => {
+                let Some(type_identifier) = &type_def.super_type else {
+                    return self.normalize_resource(self.get_serialized_object(thing)?).map(Value::Object);
+                };
+                let target = self.get_type_definition(&Self::qualified_name(type_identifier)?)?;
+                match target.kind() {
+                    _ if target.is_class() => self.normalize_resource(self.get_serialized_object(thing)?).map(Value::Object),
+                    "DateTimeScalar" => Ok(Self::normalize_date_time(thing)),
+                    "MapDeclaration" => self.normalize_map(thing, type_def, target),
+                    _ => Ok(thing.clone()),
+                }
+            }
+            _ => Ok(thing.clone()),
+        }
+    }
+
+    fn normalize_map(&self, thing: &'model_manager Value, type_def: &Property, map_def: &TypeDefinition) -> Result<Value, ValidationError> {
+        let obj = self.get_serialized_object(thing)?;
+        let (Some(key_type), Some(value_type)) = (&map_def.inner.key, &map_def.inner.value) else {
+            return Ok(thing.clone());
+        };
+        let key_property = Self::map_entry_property(key_type, "MapKeyType", format!("{} key", type_def.name));
+
+        // DateTime keys for the same instant normalize to the same key, which would lose an entry.
+        let mut original_keys: HashMap<String, &String> = HashMap::new();
+        let mut normalized = JsonObject::new();
+        for (original_key, value) in obj {
+            if original_key == "$class" {
+                normalized.insert(original_key.clone(), value.clone());
+                continue;
+            }
+            let key = match self.normalize_value(&key_property, &Value::String(original_key.clone()))? {
+                Value::String(key) => key,
+                _ => original_key.clone(),
+            };
+            if let Some(first_key) = original_keys.insert(key.clone(), original_key) {
+                return Err(ValidationError::InvalidDateTime {
+                    property: key_property.name.clone(),
+                    value: original_key.clone(),
+                    reason: format!("the same instant as key '{}', both normalize to '{}'", first_key, key),
+                });
+            }
+            let value_property = Self::map_entry_property(value_type, "MapValueType", format!("{}[{}]", type_def.name, key));
+            normalized.insert(key, self.normalize_value(&value_property, value)?);
+        }
+        Ok(Value::Object(normalized))
+    }
+
+    /// The default value of a property, or of the scalar typing it.
+    /// Defaults of properties typed with a scalar are strings read as the scalar's primitive.
+    fn get_default_value(&self, type_def: &Property) -> Result<Option<Value>, ValidationError> {
+        let target = match &type_def.super_type {
+            Some(type_identifier) if type_def.class.ends_with(".ObjectProperty") => {
+                Some(self.get_type_definition(&Self::qualified_name(type_identifier)?)?)
+            }
+            _ => None,
+        };
+        let Some(default_value) = &type_def.default_value else {
+            return Ok(target.and_then(|target| target.inner.default_value.clone()));
+        };
+        let parsed = default_value
+            .as_str()
+            .zip(target.and_then(|target| target.kind().strip_suffix("Scalar")))
+            .and_then(|(default_value, primitive)| parse_default_value(primitive, default_value));
+        Ok(Some(parsed.unwrap_or_else(|| default_value.clone())))
+    }
+
+    fn normalize_date_time(thing: &Value) -> Value {
         thing
-            .as_f64()
-            .ok_or(ValidationError::UnexpectedType {
-                expected: "Double".to_string(),
-            }).map(|_| ())
+            .as_str()
+            .and_then(|value| DateTime::parse(value).ok())
+            .map(|date_time| Value::String(date_time.to_utc_string()))
+            .unwrap_or_else(|| thing.clone())
     }
+}
 
-    fn validate_object_property(&self, thing: &'model_manager Value) -> Result<(), ValidationError> {
+/// Functions related to datasets of instances.
+impl<'model_manager> ModelManager {
+    /// The identity of a valid instance of an identified type.
+    pub fn get_identity(&self, thing: &'model_manager Value) -> Result<Option<Identity>, ValidationError> {
         let obj = self.get_serialized_object(thing)?;
-        self.validate_resource(obj)?;
+        let class_name = self.get_class_name(obj)?;
+        let type_def = self.get_type_definition(class_name)?;
+        let hierarchy = self.get_hierarchy_properties(type_def)?;
+        let Some(identified) = hierarchy.identified else {
+            return Ok(None);
+        };
+
+        // The identity is declared by the topmost identified type of the hierarchy.
+        let identified_type = self
+            .get_supertype_definitions(type_def)?
+            .into_iter()
+            .rfind(|ancestor| ancestor.inner.identified.is_some())
+            .unwrap_or(type_def);
+
+        let field = identified.name.as_deref().unwrap_or("$identifier");
+        Ok(obj.get(field).and_then(Value::as_str).map(|identifier| Identity {
+            class_name: class_name.to_string(),
+            identified_type: identified_type.full_name(),
+            identifier: identifier.to_string(),
+        }))
+    }
+
+    /// The targets of the relationships of a valid instance, including those of nested objects and maps.
+    pub fn get_references(&self, thing: &'model_manager Value) -> Result<Vec<Reference>, ValidationError> {
+        let mut references = vec![];
+        self.collect_references(self.get_serialized_object(thing)?, "", &mut references)?;
+        Ok(references)
+    }
+
+    fn collect_references(&self, thing: &'model_manager JsonObject, path: &str, references: &mut Vec<Reference>) -> Result<(), ValidationError> {
+        let hierarchy = self.get_hierarchy_properties(self.get_type_definition(self.get_class_name(thing)?)?)?;
+        for (name, value) in thing {
+            let Some(property) = hierarchy.expected.get(name) else {
+                continue;
+            };
+            let path = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+            let values = match value.as_array() {
+                Some(values) if property.is_array => values.iter().collect(),
+                _ => vec![value],
+            };
+            for value in values {
+                self.collect_value_references(property, value, &path, references)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn collect_value_references(&self, type_def: &Property, thing: &'model_manager Value, path: &str, references: &mut Vec<Reference>) -> Result<(), ValidationError> {
+        match type_def.class.rsplit('.').next().unwrap_or_default() {
+            "RelationshipProperty" => {
+                let Some(uri) = thing.as_str().and_then(|value| RelationshipUri::parse(value).ok()) else {
+                    return Ok(());
+                };
+                // Bare identifiers refer to instances of the declared type.
+                let type_name = match (&uri.type_name, &type_def.super_type) {
+                    (Some(_), Some(type_identifier)) => self.resolve_relationship_type(&uri, Some(&Self::qualified_name(type_identifier)?)).ok(),
+                    (Some(_), None) => self.resolve_relationship_type(&uri, None).ok(),
+                    (None, Some(type_identifier)) => Some(Self::qualified_name(type_identifier)?),
+                    (None, None) => None,
+                };
+                if let Some(type_name) = type_name {
+                    references.push(Reference {
+                        property: path.to_string(),
+                        type_name,
+                        identifier: uri.identifier,
+                    });
+                }
+            }
+            "ObjectProperty" => {
+                let target = match &type_def.super_type {
+                    Some(type_identifier) => Some(self.get_type_definition(&Self::qualified_name(type_identifier)?)?),
+                    None => None,
+                };
+                match target {
+                    Some(target) if target.kind() == "MapDeclaration" => {
+                        let Some(value_type) = &target.inner.value else {
+                            return Ok(());
+                        };
+                        for (key, value) in self.get_serialized_object(thing)?.iter().filter(|(key, _)| key.as_str() != "$class") {
+                            let value_property = Self::map_entry_property(value_type, "MapValueType", key.clone());
+                            self.collect_value_references(&value_property, value, &format!("{}[{}]", path, key), references)?;
+                        }
+                    }
+                    Some(target) if !target.is_class() => {}
+                    _ => self.collect_references(self.get_serialized_object(thing)?, path, references)?,
+                }
+            }
+            _ => {}
+        }
         Ok(())
     }
 }
@@ -281,7 +859,7 @@ impl<'model_manager> ModelManager {
         let type_map = parsed_definitions
             .iter()
             .map(|def| def.as_ref().ok().unwrap())
-            .map(|def| (format!("{}.{}", namespace, def.name), TypeDefinition::new(def.clone())))
+            .map(|def| (format!("{}.{}", namespace, def.name), TypeDefinition::new(namespace, def.clone())))
             .collect::<HashMap<String, TypeDefinition>>();
 
         Ok(type_map)
@@ -291,10 +869,10 @@ impl<'model_manager> ModelManager {
         let mut cache = HashMap::<String, Regex>::new();
         type_registry.values().for_each(|type_def| {
 
-            type_def.get_string_validator_patterns().iter().for_each(|pattern| {
-                let pattern = &pattern;
-                if let Ok(re) = Regex::new(pattern) {
-                    cache.insert(pattern.to_string(), re);
+            type_def.get_string_validators().iter().for_each(|validator| {
+                let pattern = validator.pattern.as_deref().unwrap_or_default();
+                if let (Some(key), Ok(re)) = (validator.regex_key(), compile_pattern(pattern, &validator.flags)) {
+                    cache.insert(key, re);
                 }
             })
         });
@@ -310,15 +888,80 @@ impl<'model_manager> ModelManager {
             })
     }
 
-    fn get_supertype_definition(&self, super_type: &SuperType) -> Result<&TypeDefinition, ValidationError> {
+    fn get_supertype_definition(&self, type_def: &TypeDefinition) -> Result<&TypeDefinition, ValidationError> {
+        let super_type = type_def.get_supertype().ok_or_else(|| ValidationError::MissingSuperTypeDefinition {
+            name: type_def.full_name(),
+        })?;
         let class_name = if let Some(ns) = &super_type.namespace {
             format!{"{}.{}", ns, super_type.name}
         } else {
-            format!{"{}.{}", CONCERTO_METAMODEL_NAMESPACE, super_type.name}
+            format!{"{}.{}", type_def.namespace, super_type.name}
         };
         self.get_type_definition(&class_name)
     }
 
+    /// Returns `true` if `class_name` is `target` or one of its subtypes.
+    pub(crate) fn is_assignable(&self, class_name: &str, target: &str) -> bool {
+        let mut current = self.type_registry.get(class_name);
+        let mut visited = HashSet::new();
+        while let Some(type_def) = current {
+            let full_name = type_def.full_name();
+            if full_name == target {
+                return true;
+            }
+            if !visited.insert(full_name) || !type_def.has_supertype() {
+                return false;
+            }
+            current = self.get_supertype_definition(type_def).ok();
+        }
+        false
+    }
+
+    fn qualified_name(type_identifier: &SuperType) -> Result<String, ValidationError> {
+        match &type_identifier.namespace {
+            Some(namespace) => Ok(format!("{}.{}", namespace, type_identifier.name)),
+            None => Err(ValidationError::MissingTypeDefinition {
+                name: type_identifier.name.clone(),
+            }),
+        }
+    }
+
+    /// Adds the namespace to the supertype and property types of a declaration,
+    /// resolving names against the local declarations and then the imports.
+    fn qualify_type_names(type_def: &mut TypeDefinition, model: &Value, known_types: &HashSet<String>) {
+        let namespace = type_def.namespace.clone();
+        let qualify = |type_identifier: &mut SuperType| {
+            if type_identifier.namespace.is_some() {
+                return;
+            }
+            if let Some((resolved_namespace, name)) = Self::resolve_type_name(model, &namespace, &type_identifier.name, known_types) {
+                type_identifier.namespace = Some(resolved_namespace.to_string());
+                type_identifier.name = name.to_string();
+            }
+        };
+
+        type_def.inner.super_type.iter_mut().for_each(qualify);
+        type_def
+            .inner
+            .properties
+            .iter_mut()
+            .filter_map(|property| property.super_type.as_mut())
+            .for_each(qualify);
+        [&mut type_def.inner.key, &mut type_def.inner.value]
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.super_type.as_mut())
+            .for_each(qualify);
+    }
+
+    fn resolve_type_name<'a>(model: &'a Value, namespace: &'a str, name: &'a str, known_types: &HashSet<String>) -> Option<(&'a str, &'a str)> {
+        if known_types.contains(&format!("{}.{}", namespace, name)) {
+            return Some((namespace, name));
+        }
+        let imports = model.as_object().into_iter().flat_map(|model| objects(model, "imports"));
+        resolve_import(imports, name, |import_namespace| known_types.contains(&format!("{}.{}", import_namespace, name)))
+    }
+
     fn get_class_name(&self, thing: &'model_manager JsonObject) -> Result<&'model_manager str, ValidationError> {
         thing.get("$class").ok_or(ValidationError::MissingRequiredProperty {
             property: "$class".to_string(),
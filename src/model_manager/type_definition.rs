@@ -5,19 +5,45 @@
 //! to the JS implementation 1-1.
 
 use std::collections::HashMap;
-use crate::model_manager::ast_structures::{ConceptDeclaration, Property, SuperType};
+use crate::model_manager::ast_structures::{ConceptDeclaration, Property, SuperType, Validator};
 
 pub(crate) struct TypeDefinition {
+    pub namespace: String,
     pub inner: ConceptDeclaration,
 }
 
 impl TypeDefinition {
-    pub fn new(concept_declaration: ConceptDeclaration) -> Self {
+    pub fn new(namespace: &str, concept_declaration: ConceptDeclaration) -> Self {
         TypeDefinition {
+            namespace: namespace.to_string(),
             inner: concept_declaration,
         }
     }
 
+    /// The fully qualified name, e.g. `concerto.metamodel@1.0.0.Model`.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.namespace, self.inner.name)
+    }
+
+    /// The unqualified name of the declaration `$class`, e.g. `ConceptDeclaration`.
+    pub fn kind(&self) -> &str {
+        self.inner.class.rsplit('.').next().unwrap_or_default()
+    }
+
+    /// Returns `true` for declarations whose instances are JSON objects,
+    /// i.e. concepts, assets, participants, transactions and events.
+    pub fn is_class(&self) -> bool {
+        matches!(
+            self.kind(),
+            "ConceptDeclaration" | "AssetDeclaration" | "ParticipantDeclaration" | "TransactionDeclaration" | "EventDeclaration"
+        )
+    }
+
+    /// Returns `true` for transactions and events, whose instances carry a `$timestamp`.
+    pub fn has_timestamp(&self) -> bool {
+        matches!(self.kind(), "TransactionDeclaration" | "EventDeclaration")
+    }
+
     /// All the properties that are part of the `ConceptDeclaration`.
     pub fn expected_properties(&self) -> HashMap<String, &Property> {
         self.inner.properties.iter().map(|x| (x.name.clone(), x)).collect()
@@ -41,15 +67,15 @@ impl TypeDefinition {
         }
     }
 
-    /// Returns all the patterns in `StringProperty` objects.
-    /// This is used for pre-compiling [`Regex`](regex::Regex) objects.
-    pub(crate) fn get_string_validator_patterns(&self) -> Vec<String> {
-        self.expected_properties().values().filter(|x| {
-            x.validator.is_some()
-        })
-            .map(|x| {
-                x.validator.clone().unwrap().pattern
-            })
+    /// Returns all the regex validators of `StringProperty` objects and of a `StringScalar`.
+    /// This is used for pre-compiling [`Regex`](fancy_regex::Regex) objects.
+    pub(crate) fn get_string_validators(&self) -> Vec<&Validator> {
+        self.inner
+            .properties
+            .iter()
+            .filter_map(|x| x.validator.as_ref())
+            .chain(self.inner.validator.as_ref())
+            .filter(|x| x.pattern.is_some())
             .collect::<Vec<_>>()
     }
 }
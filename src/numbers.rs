@@ -0,0 +1,113 @@
+//! Exact reading of JSON numbers for the integral types of Concerto.
+//! JSON numbers are parsed with `arbitrary_precision`, so the text of a number is kept as written
+//! and numbers beyond 64 bits, e.g. `1e400`, can be reported instead of failing the parse.
+//! `1.0`, `1e3` and `100e-2` are integral: they are read from their decimal digits, not through `f64`.
+
+use serde_json::Number;
+
+/// An integral JSON number.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Integral {
+    pub value: i128,
+    /// Whether the number was written with a fraction or an exponent, e.g. `1.0` or `1e3`.
+    pub decimal: bool,
+}
+
+impl Integral {
+    pub fn parse(number: &Number) -> Result<Self, String> {
+        let text = number.to_string();
+        let invalid = |reason: &str| format!("{} {}", text, reason);
+
+        let unsigned = text.strip_prefix('-').unwrap_or(&text);
+        let (mantissa, exponent) = match unsigned.split_once(['e', 'E']) {
+            Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().unwrap_or(if exponent.starts_with('-') { i64::MIN } else { i64::MAX })),
+            None => (unsigned, 0),
+        };
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let decimal = mantissa.len() != unsigned.len() || !fraction.is_empty();
+
+        // `12.50e1` has the digits `1250` scaled by 10^(1 - 2), trailing zeros only move the scale.
+        let digits = format!("{}{}", whole, fraction);
+        let significant = digits.trim_start_matches('0').trim_end_matches('0');
+        if significant.is_empty() {
+            return Ok(Integral { value: 0, decimal });
+        }
+        let trailing_zeros = digits.len() - digits.trim_end_matches('0').len();
+        let scale = exponent.saturating_sub(fraction.len() as i64).saturating_add(trailing_zeros as i64);
+        if scale < 0 {
+            return Err(invalid("is not an integral number"));
+        }
+
+        let value = u32::try_from(scale)
+            .ok()
+            .and_then(|scale| 10i128.checked_pow(scale))
+            .zip(significant.parse::<i128>().ok())
+            .and_then(|(power, significant)| significant.checked_mul(power))
+            .ok_or_else(|| invalid("is too large for an integral type"))?;
+        let value = if text.starts_with('-') { -value } else { value };
+        Ok(Integral { value, decimal })
+    }
+
+    /// Checks the value fits in `bits`, e.g. 32 for an Integer.
+    pub fn check_bits(&self, bits: u32, type_name: &str) -> Result<i64, String> {
+        let (min, max) = (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1);
+        if (min..=max).contains(&self.value) {
+            Ok(self.value as i64)
+        } else {
+            Err(format!("{} is outside the range of a {}-bit {}, {} to {}", self.value, bits, type_name, min, max))
+        }
+    }
+}
+
+/// Reads a validator bound or other integral JSON number as an `i64`, accepting `1.0` and `1e3`.
+pub(crate) fn as_integral(number: &Number) -> Option<i64> {
+    Integral::parse(number).ok().and_then(|integral| i64::try_from(integral.value).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn parse(text: &str) -> Result<Integral, String> {
+        Integral::parse(&Number::from_str(text).unwrap())
+    }
+
+    #[test]
+    fn test_integral_numbers() {
+        for (text, value, decimal) in [
+            ("0", 0, false),
+            ("-0.0", 0, true),
+            ("42", 42, false),
+            ("-42", -42, false),
+            ("1.0", 1, true),
+            ("1e3", 1000, true),
+            ("12.50e1", 125, true),
+            ("100e-2", 1, true),
+            ("9223372036854775808", 9223372036854775808, false),
+        ] {
+            assert_eq!(parse(text), Ok(Integral { value, decimal }), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_non_integral_numbers() {
+        assert_eq!(parse("1.5"), Err("1.5 is not an integral number".to_string()));
+        assert_eq!(parse("1e-1"), Err("1e-1 is not an integral number".to_string()));
+        assert!(parse("1e400").is_err());
+        assert!(parse("123456789012345678901234567890123456789012").is_err());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let integer = |text| parse(text).unwrap().check_bits(32, "Integer");
+        assert_eq!(integer("2147483647"), Ok(2147483647));
+        assert_eq!(integer("-2147483648"), Ok(-2147483648));
+        assert_eq!(
+            integer("2147483648"),
+            Err("2147483648 is outside the range of a 32-bit Integer, -2147483648 to 2147483647".to_string())
+        );
+        assert_eq!(parse("-9223372036854775808").unwrap().check_bits(64, "Long"), Ok(i64::MIN));
+        assert!(parse("9223372036854775808").unwrap().check_bits(64, "Long").is_err());
+    }
+}
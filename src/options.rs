@@ -0,0 +1,36 @@
+//! Options shared by the validators, the model manager and the semantic checks.
+
+/// Policies applied to instance values.
+/// Options are set on [`InstanceOptions::default`] with the builder methods,
+/// so that new ones can be added without breaking callers.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct InstanceOptions {
+    /// Accept `DateTime` values without a time, e.g. `2024-01-31`.
+    pub allow_date_only: bool,
+    /// Reject `DateTime` values whose offset is not UTC, e.g. `2024-01-31T10:00:00+02:00`.
+    pub require_utc: bool,
+    /// Reject integral numbers written with a fraction or an exponent, e.g. `1.0` or `1e3`,
+    /// in `Integer` and `Long` fields. Like JS Concerto, they are accepted by default.
+    pub strict_integers: bool,
+}
+
+impl InstanceOptions {
+    /// See [`InstanceOptions::allow_date_only`](#structfield.allow_date_only).
+    pub fn allow_date_only(mut self, allow_date_only: bool) -> Self {
+        self.allow_date_only = allow_date_only;
+        self
+    }
+
+    /// See [`InstanceOptions::require_utc`](#structfield.require_utc).
+    pub fn require_utc(mut self, require_utc: bool) -> Self {
+        self.require_utc = require_utc;
+        self
+    }
+
+    /// See [`InstanceOptions::strict_integers`](#structfield.strict_integers).
+    pub fn strict_integers(mut self, strict_integers: bool) -> Self {
+        self.strict_integers = strict_integers;
+        self
+    }
+}
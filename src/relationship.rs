@@ -0,0 +1,135 @@
+//! Relationship values of instances.
+//! Concerto serializes a relationship as a URI naming the type and the identifier
+//! of the target, e.g. `resource:org.acme@1.0.0.Driver#D-123`, with the identifier
+//! percent-encoded like `encodeURIComponent` does. A bare identifier, e.g. `D-123`,
+//! refers to an instance of the declared type, as in the JS `Relationship.fromURI`.
+
+/// A parsed relationship value.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RelationshipUri {
+    /// The fully qualified type, e.g. `org.acme@1.0.0.Driver`, `None` for bare identifiers.
+    pub type_name: Option<String>,
+    /// The decoded identifier of the target.
+    pub identifier: String,
+}
+
+/// The target of a relationship of an instance, with its type resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Reference {
+    /// The path of the relationship in the instance, e.g. `driver` or `fleet.drivers[first]`.
+    pub property: String,
+    /// The fully qualified type, e.g. `org.acme@1.0.0.Driver`.
+    pub type_name: String,
+    pub identifier: String,
+}
+
+/// The identity of an instance of an identified type.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Identity {
+    /// The fully qualified type of the instance, e.g. `org.acme@1.0.0.TruckDriver`.
+    pub class_name: String,
+    /// The type declaring the identity, e.g. `org.acme@1.0.0.Driver`.
+    /// Identifiers are unique among the instances of this type and of its subtypes.
+    pub identified_type: String,
+    pub identifier: String,
+}
+
+const RESOURCE_SCHEME: &str = "resource:";
+
+impl RelationshipUri {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let (type_name, identifier) = match value.strip_prefix(RESOURCE_SCHEME) {
+            Some(rest) => {
+                let (type_name, identifier) = rest
+                    .split_once('#')
+                    .ok_or_else(|| "expected resource:<namespace>.<type>#<identifier>".to_string())?;
+                if split_type_name(type_name).is_none() {
+                    return Err(format!("'{}' is not a fully qualified type name", type_name));
+                }
+                (Some(type_name.to_string()), identifier)
+            }
+            None => (None, value),
+        };
+
+        if identifier.contains('#') {
+            return Err("the identifier contains an unescaped '#'".to_string());
+        }
+        let identifier = percent_decode(identifier)?;
+        if identifier.trim().is_empty() {
+            return Err("the identifier is empty".to_string());
+        }
+
+        Ok(RelationshipUri { type_name, identifier })
+    }
+
+    /// Returns the namespace part of the type name.
+    pub fn namespace(&self) -> Option<&str> {
+        self.type_name.as_deref().and_then(split_type_name).map(|(namespace, _)| namespace)
+    }
+
+    /// Returns the short name part of the type name.
+    pub fn name(&self) -> Option<&str> {
+        self.type_name.as_deref().and_then(split_type_name).map(|(_, name)| name)
+    }
+}
+
+/// Splits `org.acme@1.0.0.Driver` into its namespace and name. Names cannot start
+/// with a digit, which tells them apart from the last part of a version.
+fn split_type_name(type_name: &str) -> Option<(&str, &str)> {
+    let (namespace, name) = type_name.rsplit_once('.')?;
+    let valid_name = name.chars().next().is_some_and(|first| !first.is_ascii_digit()) && !name.contains('@');
+    (!namespace.is_empty() && valid_name).then_some((namespace, name))
+}
+
+fn percent_decode(value: &str) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut input = value.bytes();
+    while let Some(byte) = input.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        let hex = [input.next(), input.next()];
+        let decoded = match hex {
+            [Some(high), Some(low)] => std::str::from_utf8(&[high, low]).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        bytes.push(decoded.ok_or_else(|| format!("'{}' has an invalid percent-encoding", value))?);
+    }
+    String::from_utf8(bytes).map_err(|_| format!("'{}' does not decode to UTF-8", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_uris() {
+        let uri = RelationshipUri::parse("resource:org.acme@1.0.0.Driver#D-123").unwrap();
+        assert_eq!(uri.type_name.as_deref(), Some("org.acme@1.0.0.Driver"));
+        assert_eq!((uri.namespace(), uri.name()), (Some("org.acme@1.0.0"), Some("Driver")));
+        assert_eq!(uri.identifier, "D-123");
+
+        let encoded = RelationshipUri::parse("resource:org.acme.Driver#jane%40example.org%20%C3%A9").unwrap();
+        assert_eq!(encoded.identifier, "jane@example.org é");
+
+        let bare = RelationshipUri::parse("D-123").unwrap();
+        assert_eq!((bare.type_name, bare.identifier.as_str()), (None, "D-123"));
+    }
+
+    #[test]
+    fn test_malformed_uris() {
+        for invalid in [
+            "resource:org.acme.Driver",
+            "resource:Driver#D-123",
+            "resource:org.acme@1.0.0#D-123",
+            "resource:org.acme.Driver#",
+            "resource:org.acme.Driver#a#b",
+            "resource:org.acme.Driver#%G1",
+            "resource:org.acme.Driver#%FF",
+            "",
+        ] {
+            assert!(RelationshipUri::parse(invalid).is_err(), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Rule IDs and rule sets.
+//!
+//! Every semantic check and lint rule belongs to a [`Rule`] with a stable ID,
+//! e.g. `unused-import`. A [`RuleSet`] overrides the default severity of rules
+//! or turns them off, and can be built in code or loaded from TOML:
+//!
+//! ```toml
+//! [rules]
+//! unused-import = "error"
+//! missing-description = "off"
+//! ```
+//!
+//! Findings of a rule can also be suppressed inline with a `@Suppress("rule-id")`
+//! decorator on the offending declaration, or on the model for all its declarations.
+//! Errors of the metamodel checks have no rule and are always reported.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::diagnostics::Severity;
+use crate::error::{ValidationError, ValidationResult};
+
+/// Name of the decorator suppressing rules inline.
+pub const SUPPRESS_DECORATOR: &str = "Suppress";
+
+/// A configurable check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    Identifier,
+    Namespace,
+    VersionedNamespace,
+    Validator,
+    DefaultValue,
+    Enum,
+    RelationshipTarget,
+    UndeclaredType,
+    Map,
+    Identity,
+    Supertype,
+    Decorator,
+    UnusedImport,
+    UnreferencedDeclaration,
+    PascalCaseDeclaration,
+    CamelCaseProperty,
+    EmptyConcept,
+    MissingDescription,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 18] = [
+        Rule::Identifier,
+        Rule::Namespace,
+        Rule::VersionedNamespace,
+        Rule::Validator,
+        Rule::DefaultValue,
+        Rule::Enum,
+        Rule::RelationshipTarget,
+        Rule::UndeclaredType,
+        Rule::Map,
+        Rule::Identity,
+        Rule::Supertype,
+        Rule::Decorator,
+        Rule::UnusedImport,
+        Rule::UnreferencedDeclaration,
+        Rule::PascalCaseDeclaration,
+        Rule::CamelCaseProperty,
+        Rule::EmptyConcept,
+        Rule::MissingDescription,
+    ];
+
+    /// The stable ID used in rule sets and `@Suppress` decorators.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Rule::Identifier => "identifier",
+            Rule::Namespace => "namespace",
+            Rule::VersionedNamespace => "versioned-namespace",
+            Rule::Validator => "validator",
+            Rule::DefaultValue => "default-value",
+            Rule::Enum => "enum",
+            Rule::RelationshipTarget => "relationship-target",
+            Rule::UndeclaredType => "undeclared-type",
+            Rule::Map => "map",
+            Rule::Identity => "identity",
+            Rule::Supertype => "supertype",
+            Rule::Decorator => "decorator",
+            Rule::UnusedImport => "unused-import",
+            Rule::UnreferencedDeclaration => "unreferenced-declaration",
+            Rule::PascalCaseDeclaration => "pascal-case-declaration",
+            Rule::CamelCaseProperty => "camel-case-property",
+            Rule::EmptyConcept => "empty-concept",
+            Rule::MissingDescription => "missing-description",
+        }
+    }
+
+    /// Semantic checks are errors, lint rules are warnings or informational.
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            Rule::UnusedImport | Rule::PascalCaseDeclaration | Rule::CamelCaseProperty | Rule::EmptyConcept => Severity::Warning,
+            Rule::UnreferencedDeclaration | Rule::MissingDescription => Severity::Info,
+            _ => Severity::Error,
+        }
+    }
+
+    /// The rule reporting an error, `None` for errors that cannot be configured.
+    pub fn of(error: &ValidationError) -> Option<Rule> {
+        use ValidationError::*;
+        let rule = match error {
+            InvalidIdentifier { .. } | ReservedIdentifier { .. } => Rule::Identifier,
+            InvalidNamespace { .. } | InvalidNamespaceVersion { .. } => Rule::Namespace,
+            UnversionedNamespace { .. } => Rule::VersionedNamespace,
+            InvalidValidatorRange { .. } | InvalidValidatorBound { .. } | InvalidRegexPattern { .. } => Rule::Validator,
+            InvalidDefaultValue { .. } => Rule::DefaultValue,
+            InvalidEnumDeclaration { .. } | DuplicateEnumValue { .. } => Rule::Enum,
+            InvalidRelationshipTarget { .. } | UnidentifiedRelationshipTarget { .. } => Rule::RelationshipTarget,
+            UndeclaredType { .. } => Rule::UndeclaredType,
+            InvalidMapDeclaration { .. } => Rule::Map,
+            InvalidIdentifyingField { .. } | IdentityRedeclared { .. } => Rule::Identity,
+            IncompatibleSupertype { .. } | ScalarSupertype { .. } | CyclicSupertype { .. } => Rule::Supertype,
+            UnknownDecorator { .. } | InvalidDecoratorArguments { .. } => Rule::Decorator,
+            UnusedImport { .. } => Rule::UnusedImport,
+            UnreferencedDeclaration { .. } => Rule::UnreferencedDeclaration,
+            NonPascalCaseDeclaration { .. } => Rule::PascalCaseDeclaration,
+            NonCamelCaseProperty { .. } => Rule::CamelCaseProperty,
+            EmptyConcept { .. } => Rule::EmptyConcept,
+            MissingDescription { .. } => Rule::MissingDescription,
+            _ => return None,
+        };
+        Some(rule)
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
+
+impl FromStr for Rule {
+    type Err = ValidationError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Rule::ALL
+            .into_iter()
+            .find(|rule| rule.id() == id)
+            .ok_or_else(|| ValidationError::InvalidRuleConfiguration {
+                reason: format!("unknown rule '{}'", id),
+            })
+    }
+}
+
+/// Severity overrides for rules, `None` turning a rule off.
+/// Rules that are not configured keep their [default severity](Rule::default_severity).
+#[derive(Debug, Clone, Default)]
+pub struct RuleSet {
+    levels: HashMap<Rule, Option<Severity>>,
+}
+
+#[derive(Deserialize)]
+struct RuleSetFile {
+    #[serde(default)]
+    rules: BTreeMap<String, String>,
+}
+
+impl RuleSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reports the findings of `rule` with the given severity.
+    pub fn severity(mut self, rule: Rule, severity: Severity) -> Self {
+        self.levels.insert(rule, Some(severity));
+        self
+    }
+
+    /// Stops reporting the findings of `rule`.
+    pub fn disable(mut self, rule: Rule) -> Self {
+        self.levels.insert(rule, None);
+        self
+    }
+
+    /// Loads a rule set from the `[rules]` table of a TOML document,
+    /// mapping rule IDs to `"error"`, `"warning"`, `"info"` or `"off"`.
+    pub fn from_toml(toml: &str) -> ValidationResult<Self> {
+        let file: RuleSetFile = toml::from_str(toml).map_err(|e| ValidationError::InvalidRuleConfiguration {
+            reason: e.message().to_string(),
+        })?;
+
+        let mut rule_set = Self::new();
+        for (id, level) in file.rules {
+            let rule = id.parse()?;
+            rule_set = match level.as_str() {
+                "error" => rule_set.severity(rule, Severity::Error),
+                "warning" => rule_set.severity(rule, Severity::Warning),
+                "info" => rule_set.severity(rule, Severity::Info),
+                "off" => rule_set.disable(rule),
+                _ => {
+                    return Err(ValidationError::InvalidRuleConfiguration {
+                        reason: format!("unknown level '{}' for rule '{}', expected error, warning, info or off", level, id),
+                    })
+                }
+            };
+        }
+        Ok(rule_set)
+    }
+
+    /// The severity findings of `rule` are reported with, `None` if it is turned off.
+    pub fn level(&self, rule: Rule) -> Option<Severity> {
+        self.levels.get(&rule).copied().unwrap_or(Some(rule.default_severity()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_ids_round_trip() {
+        for rule in Rule::ALL {
+            assert_eq!(rule.id().parse::<Rule>().unwrap(), rule);
+        }
+        assert!("no-such-rule".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_builder() {
+        let rules = RuleSet::new()
+            .severity(Rule::UnusedImport, Severity::Error)
+            .disable(Rule::MissingDescription);
+        assert_eq!(rules.level(Rule::UnusedImport), Some(Severity::Error));
+        assert_eq!(rules.level(Rule::MissingDescription), None);
+        assert_eq!(rules.level(Rule::EmptyConcept), Some(Severity::Warning));
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let rules = RuleSet::from_toml(
+            r#"
+            [rules]
+            empty-concept = "error"
+            identifier = "off"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rules.level(Rule::EmptyConcept), Some(Severity::Error));
+        assert_eq!(rules.level(Rule::Identifier), None);
+
+        assert!(RuleSet::from_toml("[rules]\nempty-concept = \"loud\"").is_err());
+        assert!(RuleSet::from_toml("[rules]\nno-such-rule = \"off\"").is_err());
+        assert!(RuleSet::from_toml("[rules").is_err());
+    }
+}
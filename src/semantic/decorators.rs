@@ -0,0 +1,265 @@
+//! Decorators validated against the concepts that describe them.
+//! Following the Concerto convention, `@Foo("a", 1)` is checked against a
+//! concept named `Foo` visible from the model: its properties give the
+//! expected arguments, in order, and their types. Decorators whose concept
+//! comes from a namespace outside the validated AST are not checked.
+//! These checks only run when [`ValidationOptions::validate_decorators`] is set.
+//! `@Suppress` is not checked, see [`suppressions`](crate::semantic::suppressions).
+
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::model_manager::JsonObject;
+use crate::rules::SUPPRESS_DECORATOR;
+use crate::semantic::defaults::check_primitive_value;
+use crate::semantic::model_file::{objects, short_class_name, str_field, ModelFile, ModelSet, TypeResolution};
+use crate::semantic::scalars::{is_scalar, scalar_primitive};
+use crate::semantic::Findings;
+use crate::validator::ValidationOptions;
+
+pub(crate) fn validate(model: &ModelFile, models: &ModelSet, options: &ValidationOptions, findings: &mut Findings) {
+    if !options.validate_decorators {
+        return;
+    }
+
+    let model_context = format!("model {}", model.namespace);
+    findings.on_model(|errors| {
+        for decorator in &model.decorators {
+            check_decorator(model, models, decorator, &model_context, errors);
+        }
+    });
+
+    for declaration in &model.declarations {
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            let mut decorated = vec![(declaration_name.to_string(), *declaration)];
+            for property in objects(declaration, "properties") {
+                let property_name = str_field(property, "name").unwrap_or_default();
+                decorated.push((format!("{}.{}", declaration_name, property_name), property));
+            }
+            for part in ["key", "value"] {
+                if let Some(node) = declaration.get(part).and_then(Value::as_object) {
+                    decorated.push((format!("{} {}", declaration_name, part), node));
+                }
+            }
+
+            for (context, node) in decorated {
+                for decorator in objects(node, "decorators") {
+                    check_decorator(model, models, decorator, &context, errors);
+                }
+            }
+        });
+    }
+}
+
+fn check_decorator(model: &ModelFile, models: &ModelSet, decorator: &JsonObject, context: &str, errors: &mut Vec<ValidationError>) {
+    let name = str_field(decorator, "name").unwrap_or_default();
+    // The suppression decorator is understood by the validator itself.
+    if name == SUPPRESS_DECORATOR {
+        return;
+    }
+    let arguments = objects(decorator, "arguments").collect::<Vec<_>>();
+
+    for argument in &arguments {
+        check_type_reference(model, models, argument, name, context, errors);
+    }
+
+    let (namespace, declaration) = match models.resolve(model, name) {
+        TypeResolution::Declared { namespace, declaration } => (namespace, declaration),
+        TypeResolution::Undeclared => {
+            errors.push(ValidationError::UnknownDecorator {
+                name: name.to_string(),
+                context: context.to_string(),
+            });
+            return;
+        }
+        TypeResolution::External => return,
+    };
+
+    let mut report = |reason: String| {
+        errors.push(ValidationError::InvalidDecoratorArguments {
+            name: name.to_string(),
+            context: context.to_string(),
+            reason,
+        })
+    };
+
+    if short_class_name(declaration) != "ConceptDeclaration" {
+        report(format!("{} is a {}, decorators are described by concepts", name, short_class_name(declaration)));
+        return;
+    }
+
+    // Like `getProperties()` in JS: own properties first, then the inherited ones.
+    let (chain, complete) = models.hierarchy(namespace, declaration);
+    if !complete {
+        return;
+    }
+    let properties = chain
+        .iter()
+        .flat_map(|(namespace, declaration)| objects(declaration, "properties").map(move |property| (*namespace, property)))
+        .collect::<Vec<_>>();
+
+    if arguments.len() > properties.len() {
+        report(format!("expected at most {} argument(s), found {}", properties.len(), arguments.len()));
+        return;
+    }
+
+    for (index, (namespace, property)) in properties.iter().enumerate() {
+        let property_name = str_field(property, "name").unwrap_or_default();
+        match arguments.get(index) {
+            Some(argument) => {
+                if let Err(reason) = check_argument(models, namespace, property, argument) {
+                    report(format!("argument {} ({}): {}", index + 1, property_name, reason));
+                }
+            }
+            None if property.get("isOptional").and_then(Value::as_bool) != Some(true) => {
+                report(format!("missing argument {} ({})", index + 1, property_name));
+            }
+            None => {}
+        }
+    }
+}
+
+/// Checks that an argument literal matches the type of the decorator property it fills.
+fn check_argument(models: &ModelSet, namespace: &str, property: &JsonObject, argument: &JsonObject) -> Result<(), String> {
+    let argument_class = short_class_name(argument);
+    let (primitive, validators) = match short_class_name(property) {
+        "ObjectProperty" => {
+            let scalar = property
+                .get("type")
+                .and_then(Value::as_object)
+                .zip(models.get(namespace))
+                .and_then(|(type_identifier, model)| match models.resolve_type_identifier(model, type_identifier) {
+                    TypeResolution::Declared { declaration, .. } if is_scalar(short_class_name(declaration)) => Some(declaration),
+                    _ => None,
+                });
+            match scalar {
+                Some(scalar) => (scalar_primitive(scalar).unwrap_or_default(), scalar),
+                None => ("TypeReference", property),
+            }
+        }
+        "RelationshipProperty" => ("TypeReference", property),
+        class => (class.strip_suffix("Property").unwrap_or_default(), property),
+    };
+
+    let expected = match primitive {
+        "String" | "DateTime" => "DecoratorString",
+        "Integer" | "Long" | "Double" => "DecoratorNumber",
+        "Boolean" => "DecoratorBoolean",
+        _ => "DecoratorTypeReference",
+    };
+    if argument_class != expected {
+        return Err(format!("expected {}, found {}", expected, argument_class));
+    }
+
+    if expected == "DecoratorTypeReference" {
+        return Ok(());
+    }
+    let value = argument.get("value").unwrap_or(&Value::Null);
+    check_primitive_value(&format!("{}Property", primitive), value, validators)
+}
+
+fn check_type_reference(model: &ModelFile, models: &ModelSet, argument: &JsonObject, name: &str, context: &str, errors: &mut Vec<ValidationError>) {
+    if short_class_name(argument) != "DecoratorTypeReference" {
+        return;
+    }
+    let Some(type_identifier) = argument.get("type").and_then(Value::as_object) else {
+        return;
+    };
+    if let TypeResolution::Undeclared = models.resolve_type_identifier(model, type_identifier) {
+        errors.push(ValidationError::UndeclaredType {
+            context: format!("decorator @{} on {}", name, context),
+            name: str_field(type_identifier, "name").unwrap_or_default().to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, concept, model, property, type_identifier};
+    use serde_json::json;
+
+    fn check(decorators: Value) -> Vec<ValidationError> {
+        let mut level = property("IntegerProperty", "level");
+        level["validator"] = json!({ "$class": "concerto.metamodel@1.0.0.IntegerDomainValidator", "lower": 1, "upper": 5 });
+        let mut weight = property("IntegerProperty", "weight");
+        weight["isOptional"] = json!(true);
+        let mut person = concept("Person", json!([]));
+        person["decorators"] = decorators;
+
+        let ast = model(json!([
+            concept("Term", json!([property("StringProperty", "label"), weight])),
+            concept("Rank", json!([level])),
+            concept("Since", json!([property("DateTimeProperty", "date")])),
+            person
+        ]));
+        let options = ValidationOptions::default().validate_decorators(true);
+        test_support::check(&ast, |model, models, findings| validate(model, models, &options, findings))
+    }
+
+    fn decorator(name: &str, arguments: Value) -> Value {
+        json!({ "$class": "concerto.metamodel@1.0.0.Decorator", "name": name, "arguments": arguments })
+    }
+
+    fn string(value: &str) -> Value {
+        json!({ "$class": "concerto.metamodel@1.0.0.DecoratorString", "value": value })
+    }
+
+    fn number(value: f64) -> Value {
+        json!({ "$class": "concerto.metamodel@1.0.0.DecoratorNumber", "value": value })
+    }
+
+    #[test]
+    fn test_valid_decorators() {
+        let errors = check(json!([
+            decorator("Term", json!([string("Person")])),
+            decorator("Term", json!([string("Person"), number(2.0)]))
+        ]));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_unknown_decorator() {
+        assert!(matches!(check(json!([decorator("Unknown", json!([]))]))[..], [ValidationError::UnknownDecorator { .. }]));
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        for arguments in [
+            json!([]),
+            json!([number(1.0)]),
+            json!([string("Person"), number(1.5)]),
+            json!([string("Person"), number(3000000000.0)]),
+            json!([string("Person"), number(1.0), string("extra")]),
+        ] {
+            let errors = check(json!([decorator("Term", arguments.clone())]));
+            assert!(matches!(errors[..], [ValidationError::InvalidDecoratorArguments { .. }]), "{}: {:?}", arguments, errors);
+        }
+    }
+
+    #[test]
+    fn test_arguments_follow_property_constraints() {
+        let errors = check(json!([
+            decorator("Rank", json!([number(3.0)])),
+            decorator("Since", json!([string("2024-01-01T00:00:00Z")]))
+        ]));
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        for decorators in [json!([decorator("Rank", json!([number(6.0)]))]), json!([decorator("Since", json!([string("yesterday")]))])] {
+            let errors = check(decorators.clone());
+            assert!(matches!(errors[..], [ValidationError::InvalidDecoratorArguments { .. }]), "{}: {:?}", decorators, errors);
+        }
+    }
+
+    #[test]
+    fn test_type_reference_arguments() {
+        let reference = |name: &str| json!({
+            "$class": "concerto.metamodel@1.0.0.DecoratorTypeReference",
+            "type": type_identifier(name),
+            "isArray": false
+        });
+        let errors = check(json!([decorator("Term", json!([string("Person")])), decorator("Term", json!([reference("Missing")]))]));
+        assert!(matches!(errors[..], [ValidationError::UndeclaredType { .. }, ValidationError::InvalidDecoratorArguments { .. }]), "{:?}", errors);
+    }
+}
@@ -0,0 +1,168 @@
+//! Default values of properties and scalars.
+//! A `defaultValue` must satisfy the validators declared next to it,
+//! otherwise every instance relying on the default would be invalid.
+
+use serde_json::Value;
+
+use crate::datetime::DateTime;
+use crate::error::ValidationError;
+use crate::model_manager::JsonObject;
+use crate::numbers::Integral;
+use crate::options::InstanceOptions;
+use crate::semantic::model_file::{objects, short_class_name, str_field, ModelFile};
+use crate::semantic::validators::{check_double_value, check_integer_value, check_string_value};
+use crate::semantic::Findings;
+
+pub(crate) fn validate(model: &ModelFile, findings: &mut Findings) {
+    for declaration in &model.declarations {
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            check_default_value(declaration, declaration_name, errors);
+
+            for property in objects(declaration, "properties") {
+                let property_name = str_field(property, "name").unwrap_or_default();
+                check_default_value(property, &format!("{}.{}", declaration_name, property_name), errors);
+            }
+        });
+    }
+}
+
+fn check_default_value(node: &JsonObject, context: &str, errors: &mut Vec<ValidationError>) {
+    let Some(default_value) = node.get("defaultValue") else {
+        return;
+    };
+
+    if let Err(reason) = check_primitive_value(short_class_name(node), default_value, node) {
+        push_invalid_default(context, default_value, reason, errors);
+    }
+}
+
+/// Checks a value against the primitive type of a property or scalar `$class`,
+/// e.g. `StringProperty` or `StringScalar`, and the validators of `node`.
+/// Other classes, such as `ObjectProperty`, are not checked.
+pub(crate) fn check_primitive_value(class: &str, value: &Value, node: &JsonObject) -> Result<(), String> {
+    let primitive = class
+        .strip_suffix("Property")
+        .or_else(|| class.strip_suffix("Scalar"))
+        .unwrap_or_default();
+
+    match primitive {
+        "String" => value
+            .as_str()
+            .ok_or_else(|| "expected a String".to_string())
+            .and_then(|value| check_string_value(value, node)),
+        "Integer" => check_integral(value, 32, "Integer", node),
+        "Long" => check_integral(value, 64, "Long", node),
+        "Double" => value
+            .as_f64()
+            .ok_or_else(|| "expected a Double".to_string())
+            .and_then(|value| check_double_value(value, node)),
+        "Boolean" => value
+            .as_bool()
+            .map(|_| ())
+            .ok_or_else(|| "expected a Boolean".to_string()),
+        "DateTime" => value
+            .as_str()
+            .ok_or_else(|| "expected an ISO-8601 DateTime".to_string())
+            .and_then(check_date_time),
+        _ => Ok(()),
+    }
+}
+
+/// Integral values follow the default [`InstanceOptions`], like the instance values they stand for:
+/// `1.0` is an integral number, `1.5` or `2147483648` are not Integers.
+fn check_integral(value: &Value, bits: u32, type_name: &str, node: &JsonObject) -> Result<(), String> {
+    let number = value.as_number().ok_or_else(|| format!("expected a {}-bit {}", bits, type_name))?;
+    let value = Integral::parse(number)?.check_bits(bits, type_name)?;
+    check_integer_value(value, node)
+}
+
+pub(crate) fn push_invalid_default(context: &str, default_value: &Value, reason: String, errors: &mut Vec<ValidationError>) {
+    errors.push(ValidationError::InvalidDefaultValue {
+        context: context.to_string(),
+        value: default_value_to_string(default_value),
+        reason,
+    });
+}
+
+/// Defaults follow the default [`InstanceOptions`]: models are checked once, without knowing
+/// the options of the validators they will be added to, and the default must be a valid value for all of them.
+/// A date-only default would make instances relying on it invalid unless `allow_date_only` is set.
+/// Offsets other than UTC are accepted, `require_utc` only applies to the values written in instances.
+fn check_date_time(value: &str) -> Result<(), String> {
+    DateTime::parse(value)?.check(&InstanceOptions::default())
+}
+
+fn default_value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, concept, model};
+    use serde_json::json;
+
+    fn check(declaration: Value) -> Vec<ValidationError> {
+        test_support::check(&model(json!([declaration])), |model, _, findings| validate(model, findings))
+    }
+
+    #[test]
+    fn test_string_default_value() {
+        let property = |default: &str| concept("Person", json!([{
+            "$class": "concerto.metamodel@1.0.0.StringProperty",
+            "name": "field",
+            "defaultValue": default,
+            "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": "^[a-z]+$", "flags": "" },
+            "lengthValidator": { "$class": "concerto.metamodel@1.0.0.StringLengthValidator", "maxLength": 5 }
+        }]));
+        assert!(check(property("abc")).is_empty());
+        assert!(matches!(check(property("ABC"))[..], [ValidationError::InvalidDefaultValue { .. }]));
+        assert!(matches!(check(property("abcdef"))[..], [ValidationError::InvalidDefaultValue { .. }]));
+    }
+
+    #[test]
+    fn test_numeric_default_value() {
+        let scalar = |default: i64| json!({
+            "$class": "concerto.metamodel@1.0.0.IntegerScalar",
+            "name": "Score",
+            "defaultValue": default,
+            "validator": { "$class": "concerto.metamodel@1.0.0.IntegerDomainValidator", "lower": 0, "upper": 10 }
+        });
+        assert!(check(scalar(10)).is_empty());
+        assert!(matches!(check(scalar(11))[..], [ValidationError::InvalidDefaultValue { .. }]));
+
+        let integer = |default: Value| concept("Person", json!([{ "$class": "concerto.metamodel@1.0.0.IntegerProperty", "name": "field", "defaultValue": default }]));
+        assert!(check(integer(json!(1.0))).is_empty());
+        assert!(check(integer(json!(1e3))).is_empty());
+        for invalid in [json!(1.5), json!(2147483648i64), json!("1")] {
+            assert!(matches!(check(integer(invalid.clone()))[..], [ValidationError::InvalidDefaultValue { .. }]), "{}", invalid);
+        }
+
+        let property = concept("Person", json!([{
+            "$class": "concerto.metamodel@1.0.0.DoubleProperty",
+            "name": "field",
+            "defaultValue": -0.5,
+            "validator": { "$class": "concerto.metamodel@1.0.0.DoubleDomainValidator", "lower": 0.0 }
+        }]));
+        assert!(matches!(check(property)[..], [ValidationError::InvalidDefaultValue { .. }]));
+    }
+
+    #[test]
+    fn test_date_time_default_value() {
+        let scalar = |default: &str| json!({
+            "$class": "concerto.metamodel@1.0.0.DateTimeScalar",
+            "name": "Deadline",
+            "defaultValue": default
+        });
+        assert!(check(scalar("2024-01-31T10:00:00Z")).is_empty());
+        assert!(!check(scalar("2024-02-31T10:00:00Z")).is_empty());
+        assert!(check(scalar("2024-01-31T10:00:00+02:00")).is_empty());
+        assert!(!check(scalar("2024-01-31")).is_empty());
+        assert!(!check(scalar("2024-01-31T10:00:00")).is_empty());
+        assert!(!check(scalar("tomorrow")).is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+//! Semantic rules of enum declarations.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::model_manager::JsonObject;
+use crate::semantic::model_file::{objects, short_class_name, str_field, ModelFile, ModelSet, TypeResolution};
+use crate::semantic::Findings;
+
+pub(crate) fn validate(model: &ModelFile, models: &ModelSet, findings: &mut Findings) {
+    for declaration in &model.declarations {
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            if short_class_name(declaration) == "EnumDeclaration" {
+                check_enum_values(declaration, declaration_name, errors);
+                check_enum_supertype(model, models, declaration, declaration_name, errors);
+            }
+        });
+    }
+}
+
+/// Enum values must be `EnumProperty` nodes with unique names.
+fn check_enum_values(declaration: &JsonObject, declaration_name: &str, errors: &mut Vec<ValidationError>) {
+    let mut seen = HashSet::new();
+    for property in objects(declaration, "properties") {
+        let name = str_field(property, "name").unwrap_or_default();
+        if short_class_name(property) != "EnumProperty" {
+            errors.push(ValidationError::InvalidEnumDeclaration {
+                name: declaration_name.to_string(),
+                reason: format!("'{}' is a {}, enums can only contain EnumProperty values", name, short_class_name(property)),
+            });
+        } else if !seen.insert(name) {
+            errors.push(ValidationError::DuplicateEnumValue {
+                name: declaration_name.to_string(),
+                value: name.to_string(),
+            });
+        }
+    }
+}
+
+/// The metamodel does not allow a `superType` on enums today,
+/// but if one is present it can only be another enum.
+fn check_enum_supertype(model: &ModelFile, models: &ModelSet, declaration: &JsonObject, declaration_name: &str, errors: &mut Vec<ValidationError>) {
+    let Some(super_type) = declaration.get("superType").and_then(Value::as_object) else {
+        return;
+    };
+    if let TypeResolution::Declared { declaration: parent, .. } = models.resolve_type_identifier(model, super_type) {
+        if short_class_name(parent) != "EnumDeclaration" {
+            errors.push(ValidationError::InvalidEnumDeclaration {
+                name: declaration_name.to_string(),
+                reason: format!("enums can only extend enums, {} is a {}", str_field(parent, "name").unwrap_or_default(), short_class_name(parent)),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, model};
+    use serde_json::json;
+
+    fn check(ast: Value) -> Vec<ValidationError> {
+        test_support::check(&ast, validate)
+    }
+
+    fn enum_value(name: &str) -> Value {
+        json!({ "$class": "concerto.metamodel@1.0.0.EnumProperty", "name": name })
+    }
+
+    #[test]
+    fn test_valid_enum() {
+        let ast = model(json!([{
+            "$class": "concerto.metamodel@1.0.0.EnumDeclaration",
+            "name": "Color",
+            "properties": [enum_value("RED"), enum_value("GREEN")]
+        }]));
+        assert!(check(ast).is_empty());
+    }
+
+    #[test]
+    fn test_enum_values() {
+        let ast = model(json!([{
+            "$class": "concerto.metamodel@1.0.0.EnumDeclaration",
+            "name": "Color",
+            "properties": [
+                enum_value("RED"),
+                enum_value("RED"),
+                { "$class": "concerto.metamodel@1.0.0.StringProperty", "name": "hex", "isArray": false, "isOptional": false }
+            ]
+        }]));
+        let errors = check(ast);
+        assert!(matches!(errors[..], [ValidationError::DuplicateEnumValue { .. }, ValidationError::InvalidEnumDeclaration { .. }]));
+    }
+
+    #[test]
+    fn test_enum_supertype() {
+        let ast = model(json!([
+            {
+                "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                "name": "Base",
+                "isAbstract": true,
+                "properties": []
+            },
+            {
+                "$class": "concerto.metamodel@1.0.0.EnumDeclaration",
+                "name": "Color",
+                "superType": { "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": "Base" },
+                "properties": [enum_value("RED")]
+            }
+        ]));
+        assert!(matches!(check(ast)[..], [ValidationError::InvalidEnumDeclaration { .. }]));
+    }
+}
@@ -0,0 +1,135 @@
+//! Identifier rules for names used in a model.
+//! The grammar mirrors the `Identifier` rule of the Concerto parser,
+//! which is also the regex the metamodel puts on `Declaration.name`.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::error::ValidationError;
+use crate::semantic::model_file::{objects, short_class_name, str_field, ModelFile};
+use crate::semantic::namespaces::Namespace;
+use crate::semantic::Findings;
+
+/// Letters, `$`, `_` or an escaped code point, followed by letters, digits,
+/// combining marks, connector punctuation, ZWNJ or ZWJ.
+const IDENTIFIER_PATTERN: &str = r"^(?:\p{Lu}|\p{Ll}|\p{Lt}|\p{Lm}|\p{Lo}|\p{Nl}|\$|_|\\u[0-9A-Fa-f]{4})(?:\p{Lu}|\p{Ll}|\p{Lt}|\p{Lm}|\p{Lo}|\p{Nl}|\$|_|\\u[0-9A-Fa-f]{4}|\p{Mn}|\p{Mc}|\p{Nd}|\p{Pc}|\x{200C}|\x{200D})*$";
+
+/// Names of the system properties that every instance may carry.
+pub(crate) const RESERVED_IDENTIFIERS: [&str; 3] = ["$class", "$identifier", "$timestamp"];
+
+static IDENTIFIER_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// Returns `true` if `name` matches the Concerto identifier grammar.
+pub(crate) fn is_valid_identifier(name: &str) -> bool {
+    IDENTIFIER_REGEX
+        .get_or_init(|| Regex::new(IDENTIFIER_PATTERN).expect("identifier pattern is valid"))
+        .is_match(name)
+}
+
+pub(crate) fn validate(model: &ModelFile, findings: &mut Findings) {
+    findings.on_model(|errors| {
+        check_namespace(model.namespace, errors);
+        model
+            .imports
+            .iter()
+            .filter_map(|import| str_field(import, "namespace"))
+            .for_each(|namespace| check_namespace(namespace, errors));
+    });
+
+    for declaration in &model.declarations {
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            check_identifier(declaration_name, "declaration name", errors);
+
+            for property in objects(declaration, "properties") {
+                let name = str_field(property, "name").unwrap_or_default();
+                if short_class_name(property) == "EnumProperty" {
+                    check_identifier(name, &format!("enum value of {}", declaration_name), errors);
+                } else {
+                    check_property_name(name, &format!("property of {}", declaration_name), errors);
+                }
+            }
+        });
+    }
+}
+
+fn check_identifier(name: &str, context: &str, errors: &mut Vec<ValidationError>) {
+    if RESERVED_IDENTIFIERS.contains(&name) {
+        errors.push(ValidationError::ReservedIdentifier {
+            name: name.to_string(),
+            context: context.to_string(),
+        });
+    } else if !is_valid_identifier(name) {
+        errors.push(ValidationError::InvalidIdentifier {
+            name: name.to_string(),
+            context: context.to_string(),
+        });
+    }
+}
+
+/// Property names share the identifier grammar, but a leading `$`
+/// would clash with the system properties of serialized instances.
+fn check_property_name(name: &str, context: &str, errors: &mut Vec<ValidationError>) {
+    if name.starts_with('$') && !RESERVED_IDENTIFIERS.contains(&name) && is_valid_identifier(name) {
+        errors.push(ValidationError::ReservedIdentifier {
+            name: name.to_string(),
+            context: context.to_string(),
+        });
+    } else {
+        check_identifier(name, context, errors);
+    }
+}
+
+/// The name part of a namespace is a dot separated list of identifiers.
+/// The version part is checked in [`namespaces`](crate::semantic::namespaces).
+fn check_namespace(namespace: &str, errors: &mut Vec<ValidationError>) {
+    let name = Namespace::parse(namespace).name;
+    if let Some(segment) = name.split('.').find(|segment| !is_valid_identifier(segment)) {
+        errors.push(ValidationError::InvalidNamespace {
+            namespace: namespace.to_string(),
+            reason: format!("'{}' is not a valid identifier", segment),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier_grammar() {
+        assert!(is_valid_identifier("Person"));
+        assert!(is_valid_identifier("_private"));
+        assert!(is_valid_identifier("$value"));
+        assert!(is_valid_identifier("número2"));
+        assert!(is_valid_identifier("\\u0041bc"));
+        assert!(!is_valid_identifier(""));
+        assert!(!is_valid_identifier("2fast"));
+        assert!(!is_valid_identifier("first-name"));
+        assert!(!is_valid_identifier("first name"));
+    }
+
+    #[test]
+    fn test_namespace_segments() {
+        let mut errors = vec![];
+        check_namespace("org.example@1.0.0", &mut errors);
+        check_namespace("org.example", &mut errors);
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        check_namespace("org..example@1.0.0", &mut errors);
+        check_namespace("org.1example", &mut errors);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], ValidationError::InvalidNamespace { .. }));
+    }
+
+    #[test]
+    fn test_reserved_property_names() {
+        let mut errors = vec![];
+        check_property_name("$class", "property of Person", &mut errors);
+        check_property_name("$custom", "property of Person", &mut errors);
+        check_property_name("firstName", "property of Person", &mut errors);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|e| matches!(e, ValidationError::ReservedIdentifier { .. })));
+    }
+}
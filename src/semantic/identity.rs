@@ -0,0 +1,168 @@
+//! Semantic rules of `identified` and `identified by` declarations.
+//! The identifying field must exist in the declaration or one of its ancestors
+//! and must be a required, single `String` (or `String` scalar).
+//! Once a type is identified, its subtypes inherit that identity.
+
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::model_manager::JsonObject;
+use crate::semantic::model_file::{objects, short_class_name, str_field, ModelFile, ModelSet, TypeResolution};
+use crate::semantic::scalars::scalar_primitive;
+use crate::semantic::Findings;
+
+pub(crate) fn validate(model: &ModelFile, models: &ModelSet, findings: &mut Findings) {
+    for declaration in &model.declarations {
+        let Some(identified) = declaration.get("identified").and_then(Value::as_object) else {
+            continue;
+        };
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            let (chain, complete) = models.hierarchy(model.namespace, declaration);
+
+            let inherited = chain[1..]
+                .iter()
+                .find_map(|(_, ancestor)| ancestor.get("identified").and_then(Value::as_object).map(|identity| (ancestor, identity)));
+            if let Some((ancestor, identity)) = inherited {
+                if describe(identity) != describe(identified) {
+                    errors.push(ValidationError::IdentityRedeclared {
+                        declaration: declaration_name.to_string(),
+                        ancestor: str_field(ancestor, "name").unwrap_or_default().to_string(),
+                    });
+                }
+            }
+
+            if short_class_name(identified) != "IdentifiedBy" {
+                return;
+            }
+            let field = str_field(identified, "name").unwrap_or_default();
+            let property = chain.iter().find_map(|(namespace, declaration)| {
+                objects(declaration, "properties")
+                    .find(|property| str_field(property, "name") == Some(field))
+                    .map(|property| (*namespace, property))
+            });
+
+            let result = match property {
+                Some((namespace, property)) => check_identifying_property(models, namespace, property),
+                None if complete => Err("no such property is declared".to_string()),
+                None => Ok(()),
+            };
+            if let Err(reason) = result {
+                errors.push(ValidationError::InvalidIdentifyingField {
+                    declaration: declaration_name.to_string(),
+                    field: field.to_string(),
+                    reason,
+                });
+            }
+        });
+    }
+}
+
+fn check_identifying_property(models: &ModelSet, namespace: &str, property: &JsonObject) -> Result<(), String> {
+    if property.get("isOptional").and_then(Value::as_bool) == Some(true) {
+        return Err("it must not be optional".to_string());
+    }
+    if property.get("isArray").and_then(Value::as_bool) == Some(true) {
+        return Err("it must not be an array".to_string());
+    }
+    match short_class_name(property) {
+        "StringProperty" => Ok(()),
+        "ObjectProperty" => {
+            let type_identifier = property.get("type").and_then(Value::as_object);
+            let resolution = match (models.get(namespace), type_identifier) {
+                (Some(model), Some(type_identifier)) => models.resolve_type_identifier(model, type_identifier),
+                _ => TypeResolution::External,
+            };
+            match resolution {
+                TypeResolution::Declared { declaration, .. } if scalar_primitive(declaration) != Some("String") => {
+                    Err(format!("it must be a String, found {}", short_class_name(declaration)))
+                }
+                _ => Ok(()),
+            }
+        }
+        class => Err(format!("it must be a String, found {}", class)),
+    }
+}
+
+/// `Identified` and `IdentifiedBy` nodes compared by their kind and field.
+fn describe(identity: &JsonObject) -> (&str, Option<&str>) {
+    (short_class_name(identity), str_field(identity, "name"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, model, property, type_identifier};
+    use serde_json::json;
+
+    fn check(declarations: Value) -> Vec<ValidationError> {
+        test_support::check(&model(declarations), validate)
+    }
+
+    fn person(identified: Value, properties: Value) -> Value {
+        json!({
+            "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration",
+            "name": "Person",
+            "isAbstract": false,
+            "identified": identified,
+            "properties": properties
+        })
+    }
+
+    fn identified_by(name: &str) -> Value {
+        json!({ "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": name })
+    }
+
+    #[test]
+    fn test_identifying_field() {
+        assert!(check(json!([person(identified_by("ssn"), json!([property("StringProperty", "ssn")]))])).is_empty());
+
+        let mut optional = property("StringProperty", "ssn");
+        optional["isOptional"] = json!(true);
+
+        for declarations in [
+            json!([person(identified_by("ssn"), json!([]))]),
+            json!([person(identified_by("ssn"), json!([optional]))]),
+            json!([person(identified_by("ssn"), json!([property("IntegerProperty", "ssn")]))]),
+        ] {
+            assert!(matches!(check(declarations)[..], [ValidationError::InvalidIdentifyingField { .. }]));
+        }
+    }
+
+    #[test]
+    fn test_inherited_identifying_field() {
+        let errors = check(json!([
+            {
+                "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                "name": "Base",
+                "isAbstract": true,
+                "properties": [property("StringProperty", "ssn")]
+            },
+            {
+                "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration",
+                "name": "Person",
+                "isAbstract": false,
+                "identified": identified_by("ssn"),
+                "superType": type_identifier("Base"),
+                "properties": []
+            }
+        ]));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_identity_redeclared() {
+        let errors = check(json!([
+            person(identified_by("ssn"), json!([property("StringProperty", "ssn")])),
+            {
+                "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration",
+                "name": "Employee",
+                "isAbstract": false,
+                "identified": { "$class": "concerto.metamodel@1.0.0.Identified" },
+                "superType": type_identifier("Person"),
+                "properties": []
+            }
+        ]));
+        assert!(matches!(errors[..], [ValidationError::IdentityRedeclared { .. }]));
+    }
+}
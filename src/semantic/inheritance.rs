@@ -0,0 +1,140 @@
+//! Declaration kinds in user-model inheritance.
+//! A declaration can only extend a declaration of the same kind, e.g. an asset
+//! extends an asset, with plain concepts usable as a base for every class kind.
+//! Scalars are aliases of primitive types and can never be extended,
+//! and a declaration can never extend itself, directly or through its supertypes.
+
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::semantic::model_file::{short_class_name, str_field, ModelFile, ModelSet, TypeResolution};
+use crate::semantic::scalars::is_scalar;
+use crate::semantic::Findings;
+
+pub(crate) const CLASS_DECLARATIONS: [&str; 5] = [
+    "ConceptDeclaration",
+    "AssetDeclaration",
+    "ParticipantDeclaration",
+    "TransactionDeclaration",
+    "EventDeclaration",
+];
+
+pub(crate) fn validate(model: &ModelFile, models: &ModelSet, findings: &mut Findings) {
+    for declaration in &model.declarations {
+        let Some(super_type) = declaration.get("superType").and_then(Value::as_object) else {
+            continue;
+        };
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            let super_type_name = str_field(super_type, "name").unwrap_or_default();
+            let kind = short_class_name(declaration);
+
+            match models.resolve_type_identifier(model, super_type) {
+                TypeResolution::Declared { declaration: parent, .. } => {
+                    let super_kind = short_class_name(parent);
+                    if is_scalar(super_kind) {
+                        errors.push(ValidationError::ScalarSupertype {
+                            declaration: declaration_name.to_string(),
+                            scalar: super_type_name.to_string(),
+                        });
+                    } else if !is_compatible(kind, super_kind) {
+                        errors.push(ValidationError::IncompatibleSupertype {
+                            declaration: declaration_name.to_string(),
+                            kind: kind.to_string(),
+                            super_type: super_type_name.to_string(),
+                            super_kind: super_kind.to_string(),
+                        });
+                    }
+                }
+                TypeResolution::Undeclared => errors.push(ValidationError::UndeclaredType {
+                    context: format!("supertype of {}", declaration_name),
+                    name: super_type_name.to_string(),
+                }),
+                TypeResolution::External => {}
+            }
+
+            if let Some(cycle) = models.supertype_cycle(model.namespace, declaration) {
+                errors.push(ValidationError::CyclicSupertype {
+                    declaration: declaration_name.to_string(),
+                    cycle: cycle.join(" -> "),
+                });
+            }
+        });
+    }
+}
+
+/// Enums extending enums are covered by the enum rules.
+fn is_compatible(kind: &str, super_kind: &str) -> bool {
+    if CLASS_DECLARATIONS.contains(&kind) {
+        super_kind == kind || super_kind == "ConceptDeclaration"
+    } else {
+        kind == "EnumDeclaration"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, concept, model, type_identifier};
+    use serde_json::json;
+
+    fn check(parent: &str, child: &str) -> Vec<ValidationError> {
+        let ast = model(json!([
+            { "$class": format!("concerto.metamodel@1.0.0.{}", parent), "name": "Parent", "isAbstract": true, "properties": [] },
+            {
+                "$class": format!("concerto.metamodel@1.0.0.{}", child),
+                "name": "Child",
+                "isAbstract": false,
+                "superType": type_identifier("Parent"),
+                "properties": []
+            }
+        ]));
+        test_support::check(&ast, validate)
+    }
+
+    #[test]
+    fn test_compatible_kinds() {
+        assert!(check("AssetDeclaration", "AssetDeclaration").is_empty());
+        assert!(check("ConceptDeclaration", "ParticipantDeclaration").is_empty());
+        assert!(check("EventDeclaration", "EventDeclaration").is_empty());
+    }
+
+    #[test]
+    fn test_incompatible_kinds() {
+        for (parent, child) in [
+            ("ParticipantDeclaration", "AssetDeclaration"),
+            ("AssetDeclaration", "ConceptDeclaration"),
+            ("EnumDeclaration", "ConceptDeclaration"),
+            ("TransactionDeclaration", "EventDeclaration"),
+        ] {
+            assert!(matches!(check(parent, child)[..], [ValidationError::IncompatibleSupertype { .. }]), "{} extending {}", child, parent);
+        }
+    }
+
+    #[test]
+    fn test_cyclic_supertypes() {
+        let extending = |name: &str, super_type: &str| {
+            let mut concept = concept(name, json!([]));
+            concept["superType"] = type_identifier(super_type);
+            concept
+        };
+        let cycles = |declarations: Value| {
+            test_support::check(&model(declarations), validate)
+                .into_iter()
+                .map(|error| match error {
+                    ValidationError::CyclicSupertype { cycle, .. } => cycle,
+                    error => panic!("{:?}", error),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(cycles(json!([extending("A", "A")])), ["A -> A"]);
+        assert_eq!(cycles(json!([extending("A", "B"), extending("B", "A")])), ["A -> B -> A", "B -> A -> B"]);
+        assert_eq!(cycles(json!([extending("A", "B"), extending("B", "C"), extending("C", "A"), extending("D", "A")])), ["A -> B -> C -> A", "B -> C -> A -> B", "C -> A -> B -> C"]);
+    }
+
+    #[test]
+    fn test_scalar_supertype() {
+        assert!(matches!(check("StringScalar", "ConceptDeclaration")[..], [ValidationError::ScalarSupertype { .. }]));
+    }
+}
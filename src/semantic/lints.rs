@@ -0,0 +1,289 @@
+//! Lint rules. Unlike the other checks they report questionable models,
+//! not invalid ones, so their [rules](crate::rules::Rule) default to warnings
+//! or informational diagnostics instead of errors.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::model_manager::JsonObject;
+use crate::semantic::inheritance::CLASS_DECLARATIONS;
+use crate::semantic::model_file::{objects, short_class_name, str_field, ModelFile, ModelSet, TypeResolution};
+use crate::semantic::Findings;
+
+/// Decorator expected on every property, compared case-insensitively.
+const DESCRIPTION_DECORATOR: &str = "description";
+
+/// A type used by a model, through a `TypeIdentifier` or a decorator name.
+struct Reference<'a> {
+    /// The declaration using the type, `None` for model decorators.
+    owner: Option<&'a JsonObject>,
+    name: &'a str,
+    type_identifier: Option<&'a JsonObject>,
+}
+
+/// Declarations of any model that are used by another declaration.
+pub(crate) type Referenced = HashSet<*const JsonObject>;
+
+/// Collects the declarations referenced across all the models.
+pub(crate) fn referenced_declarations(models: &ModelSet) -> Referenced {
+    let mut referenced = HashSet::new();
+    for model in &models.models {
+        for reference in references(model) {
+            if let TypeResolution::Declared { declaration, .. } = resolve(model, models, &reference) {
+                // A declaration referring to itself is still unused.
+                if !reference.owner.is_some_and(|owner| std::ptr::eq(owner, declaration)) {
+                    referenced.insert(declaration as *const JsonObject);
+                }
+            }
+        }
+    }
+    referenced
+}
+
+pub(crate) fn validate(model: &ModelFile, models: &ModelSet, referenced: &Referenced, findings: &mut Findings) {
+    findings.on_model(|errors| check_imports(model, models, errors));
+    for declaration in &model.declarations {
+        let name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(name, |errors| {
+            if !referenced.contains(&(*declaration as *const JsonObject)) {
+                errors.push(ValidationError::UnreferencedDeclaration { name: name.to_string() });
+            }
+            check_declaration(declaration, errors);
+        });
+    }
+}
+
+fn check_imports(model: &ModelFile, models: &ModelSet, errors: &mut Vec<ValidationError>) {
+    let references = references(model);
+    let used_names: HashSet<&str> = references
+        .iter()
+        .filter(|reference| reference.type_identifier.is_none_or(|ti| !ti.contains_key("namespace")))
+        .map(|reference| reference.name)
+        .collect();
+
+    for import in &model.imports {
+        let namespace = str_field(import, "namespace").unwrap_or_default();
+        let mut unused = |name: &str| {
+            errors.push(ValidationError::UnusedImport {
+                namespace: namespace.to_string(),
+                name: name.to_string(),
+            })
+        };
+
+        match short_class_name(import) {
+            "ImportType" => {
+                let name = str_field(import, "name").unwrap_or_default();
+                if !used_names.contains(name) {
+                    unused(name);
+                }
+            }
+            "ImportTypes" => {
+                let types = import.get("types").and_then(Value::as_array).into_iter().flatten();
+                for name in types.filter_map(Value::as_str) {
+                    let local_name = objects(import, "aliasedTypes")
+                        .find(|alias| str_field(alias, "name") == Some(name))
+                        .and_then(|alias| str_field(alias, "aliasedName"))
+                        .unwrap_or(name);
+                    if !used_names.contains(local_name) {
+                        unused(name);
+                    }
+                }
+            }
+            // Only namespaces that are part of the AST tell which of their types are used.
+            "ImportAll" if models.get(namespace).is_some() => {
+                let used = references.iter().any(|reference| {
+                    matches!(resolve(model, models, reference), TypeResolution::Declared { namespace: ns, .. } if ns == namespace)
+                });
+                if !used {
+                    unused("*");
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn check_declaration(declaration: &JsonObject, errors: &mut Vec<ValidationError>) {
+    let name = str_field(declaration, "name").unwrap_or_default();
+    let class = short_class_name(declaration);
+
+    if !is_pascal_case(name) {
+        errors.push(ValidationError::NonPascalCaseDeclaration { name: name.to_string() });
+    }
+
+    let is_class = CLASS_DECLARATIONS.contains(&class);
+    let properties = objects(declaration, "properties").collect::<Vec<_>>();
+    if is_class && properties.is_empty() && !declaration.contains_key("superType") {
+        errors.push(ValidationError::EmptyConcept { name: name.to_string() });
+    }
+
+    // Enum values are conventionally upper case and documented by their enum.
+    if !is_class {
+        return;
+    }
+    for property in properties {
+        let property_name = str_field(property, "name").unwrap_or_default();
+        let context = format!("{}.{}", name, property_name);
+        if !is_camel_case(property_name) {
+            errors.push(ValidationError::NonCamelCaseProperty { context: context.clone() });
+        }
+        let documented = objects(property, "decorators")
+            .any(|decorator| str_field(decorator, "name").is_some_and(|n| n.eq_ignore_ascii_case(DESCRIPTION_DECORATOR)));
+        if !documented {
+            errors.push(ValidationError::MissingDescription {
+                context,
+                decorator: DESCRIPTION_DECORATOR.to_string(),
+            });
+        }
+    }
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_uppercase) && !name.contains('_')
+}
+
+fn is_camel_case(name: &str) -> bool {
+    name.chars().next().is_some_and(char::is_lowercase) && !name.contains('_')
+}
+
+/// Collects the types used by the declarations and decorators of a model.
+fn references<'a>(model: &ModelFile<'a>) -> Vec<Reference<'a>> {
+    let mut references = vec![];
+    for declaration in &model.declarations {
+        for value in declaration.values() {
+            collect_references(Some(declaration), value, &mut references);
+        }
+    }
+    for decorator in &model.decorators {
+        collect_node_references(None, decorator, &mut references);
+    }
+    references
+}
+
+fn collect_references<'a>(owner: Option<&'a JsonObject>, value: &'a Value, references: &mut Vec<Reference<'a>>) {
+    match value {
+        Value::Object(node) => collect_node_references(owner, node, references),
+        Value::Array(values) => {
+            for value in values {
+                collect_references(owner, value, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_node_references<'a>(owner: Option<&'a JsonObject>, node: &'a JsonObject, references: &mut Vec<Reference<'a>>) {
+    let class = short_class_name(node);
+    if class == "TypeIdentifier" || class == "Decorator" {
+        references.push(Reference {
+            owner,
+            name: str_field(node, "name").unwrap_or_default(),
+            type_identifier: (class == "TypeIdentifier").then_some(node),
+        });
+    }
+    for value in node.values() {
+        collect_references(owner, value, references);
+    }
+}
+
+fn resolve<'a>(model: &ModelFile<'a>, models: &ModelSet<'a>, reference: &Reference<'a>) -> TypeResolution<'a> {
+    match reference.type_identifier {
+        Some(type_identifier) => models.resolve_type_identifier(model, type_identifier),
+        None => models.resolve(model, reference.name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, concept, model, type_identifier};
+    use serde_json::json;
+
+    fn lint(imports: Value, declarations: Value) -> Vec<ValidationError> {
+        let mut example = model(declarations);
+        example["imports"] = imports;
+        let mut other = model(json!([concept("Address", json!([property("street")]))]));
+        other["namespace"] = json!("org.other@1.0.0");
+
+        let ast = json!({ "$class": "concerto.metamodel@1.0.0.Models", "models": [example, other] });
+        test_support::check(&ast, |model, models, findings| {
+            if model.namespace == "org.example@1.0.0" {
+                validate(model, models, &referenced_declarations(models), findings);
+            }
+        })
+    }
+
+    fn property(name: &str) -> Value {
+        let mut property = test_support::property("StringProperty", name);
+        property["decorators"] = json!([{ "$class": "concerto.metamodel@1.0.0.Decorator", "name": "Description" }]);
+        property
+    }
+
+    fn object_property(name: &str, type_name: &str) -> Value {
+        let mut property = property(name);
+        property["$class"] = json!("concerto.metamodel@1.0.0.ObjectProperty");
+        property["type"] = type_identifier(type_name);
+        property
+    }
+
+    fn import_all() -> Value {
+        json!([{ "$class": "concerto.metamodel@1.0.0.ImportAll", "namespace": "org.other@1.0.0" }])
+    }
+
+    #[test]
+    fn test_clean_model() {
+        let errors = lint(import_all(), json!([concept("Person", json!([object_property("address", "Address")]))]));
+        // The root concept is never referenced.
+        assert!(matches!(&errors[..], [ValidationError::UnreferencedDeclaration { name }] if name == "Person"));
+    }
+
+    #[test]
+    fn test_unused_imports() {
+        let imports = json!([
+            { "$class": "concerto.metamodel@1.0.0.ImportType", "namespace": "org.other@1.0.0", "name": "Address" },
+            { "$class": "concerto.metamodel@1.0.0.ImportTypes", "namespace": "org.external@1.0.0", "types": ["Used", "Unused"] }
+        ]);
+        let errors = lint(imports, json!([concept("Person", json!([object_property("other", "Used")]))]));
+        let unused = errors
+            .iter()
+            .filter_map(|e| match e {
+                ValidationError::UnusedImport { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(unused, ["Address", "Unused"]);
+
+        let errors = lint(import_all(), json!([concept("Person", json!([property("name")]))]));
+        assert!(errors.iter().any(|e| matches!(e, ValidationError::UnusedImport { name, .. } if name == "*")));
+    }
+
+    #[test]
+    fn test_self_references_do_not_count() {
+        let errors = lint(json!([]), json!([concept("Person", json!([object_property("friend", "Person")]))]));
+        assert!(errors.iter().any(|e| matches!(e, ValidationError::UnreferencedDeclaration { .. })));
+    }
+
+    #[test]
+    fn test_style_rules() {
+        let mut undocumented = property("First_name");
+        undocumented.as_object_mut().unwrap().remove("decorators");
+        let errors = lint(json!([]), json!([concept("person", json!([undocumented])), concept("Empty", json!([]))]));
+
+        let messages = errors
+            .iter()
+            .filter(|e| !matches!(e, ValidationError::UnreferencedDeclaration { .. }))
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "Declaration name person should be PascalCase",
+                "Property name person.First_name should be camelCase",
+                "Property person.First_name has no @description decorator",
+                "Declaration Empty has no properties",
+            ]
+        );
+    }
+}
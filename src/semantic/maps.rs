@@ -0,0 +1,132 @@
+//! Semantic rules of map declarations.
+//! Keys are restricted to `String`, `DateTime` or scalars of those,
+//! and object or relationship values must resolve. Relationship targets
+//! are checked in [`relationships`](crate::semantic::relationships).
+
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::model_manager::JsonObject;
+use crate::semantic::model_file::{short_class_name, str_field, ModelFile, ModelSet, TypeResolution};
+use crate::semantic::Findings;
+
+pub(crate) fn validate(model: &ModelFile, models: &ModelSet, findings: &mut Findings) {
+    for declaration in &model.declarations {
+        if short_class_name(declaration) != "MapDeclaration" {
+            continue;
+        }
+        let name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(name, |errors| {
+            let mut report = |reason: String| {
+                errors.push(ValidationError::InvalidMapDeclaration {
+                    name: name.to_string(),
+                    reason,
+                })
+            };
+
+            if let Some(key) = declaration.get("key").and_then(Value::as_object) {
+                if let Err(reason) = check_key(model, models, key) {
+                    report(reason);
+                }
+            }
+            if let Some(value) = declaration.get("value").and_then(Value::as_object) {
+                if let Err(reason) = check_value(model, models, value) {
+                    report(reason);
+                }
+            }
+        });
+    }
+}
+
+fn check_key(model: &ModelFile, models: &ModelSet, key: &JsonObject) -> Result<(), String> {
+    if short_class_name(key) != "ObjectMapKeyType" {
+        return Ok(());
+    }
+    match resolve(model, models, key) {
+        (type_name, TypeResolution::Undeclared) => Err(format!("key type {} is not declared", type_name)),
+        (type_name, TypeResolution::Declared { declaration, .. }) => match short_class_name(declaration) {
+            "StringScalar" | "DateTimeScalar" => Ok(()),
+            class => Err(format!("key type {} is a {}, keys must be String, DateTime or a scalar of those", type_name, class)),
+        },
+        (_, TypeResolution::External) => Ok(()),
+    }
+}
+
+fn check_value(model: &ModelFile, models: &ModelSet, value: &JsonObject) -> Result<(), String> {
+    let class = short_class_name(value);
+    if class != "ObjectMapValueType" && class != "RelationshipMapValueType" {
+        return Ok(());
+    }
+    match resolve(model, models, value) {
+        (type_name, TypeResolution::Undeclared) => Err(format!("value type {} is not declared", type_name)),
+        _ => Ok(()),
+    }
+}
+
+fn resolve<'a>(model: &ModelFile<'a>, models: &ModelSet<'a>, node: &'a JsonObject) -> (&'a str, TypeResolution<'a>) {
+    match node.get("type").and_then(Value::as_object) {
+        Some(type_identifier) => (
+            str_field(type_identifier, "name").unwrap_or_default(),
+            models.resolve_type_identifier(model, type_identifier),
+        ),
+        None => ("", TypeResolution::Undeclared),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, model, type_identifier};
+    use serde_json::json;
+
+    fn check(declarations: Value) -> Vec<ValidationError> {
+        test_support::check(&model(declarations), validate)
+    }
+
+    fn map(key: Value, value: Value) -> Value {
+        json!({ "$class": "concerto.metamodel@1.0.0.MapDeclaration", "name": "Lookup", "key": key, "value": value })
+    }
+
+    #[test]
+    fn test_valid_maps() {
+        let errors = check(json!([
+            { "$class": "concerto.metamodel@1.0.0.StringScalar", "name": "Code" },
+            {
+                "$class": "concerto.metamodel@1.0.0.AssetDeclaration",
+                "name": "Car",
+                "isAbstract": false,
+                "identified": { "$class": "concerto.metamodel@1.0.0.Identified" },
+                "properties": []
+            },
+            map(
+                json!({ "$class": "concerto.metamodel@1.0.0.ObjectMapKeyType", "type": type_identifier("Code") }),
+                json!({ "$class": "concerto.metamodel@1.0.0.RelationshipMapValueType", "type": type_identifier("Car") })
+            )
+        ]));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_key_types() {
+        let errors = check(json!([
+            { "$class": "concerto.metamodel@1.0.0.IntegerScalar", "name": "Count" },
+            map(
+                json!({ "$class": "concerto.metamodel@1.0.0.ObjectMapKeyType", "type": type_identifier("Count") }),
+                json!({ "$class": "concerto.metamodel@1.0.0.StringMapValueType" })
+            )
+        ]));
+        assert!(matches!(errors[..], [ValidationError::InvalidMapDeclaration { .. }]));
+    }
+
+    #[test]
+    fn test_undeclared_value_type() {
+        let errors = check(json!([
+            map(
+                json!({ "$class": "concerto.metamodel@1.0.0.StringMapKeyType" }),
+                json!({ "$class": "concerto.metamodel@1.0.0.ObjectMapValueType", "type": type_identifier("Missing") })
+            )
+        ]));
+        assert!(matches!(errors[..], [ValidationError::InvalidMapDeclaration { .. }]));
+        assert!(errors[0].to_string().contains("Lookup"));
+    }
+}
@@ -0,0 +1,147 @@
+//! Semantic validation of Concerto models.
+//!
+//! The [`ModelManager`](crate::model_manager::ModelManager) only checks that an AST
+//! is a structurally valid instance of the metamodel. The checks in this module
+//! run afterwards and enforce the rules that the JS implementation applies when
+//! a model file is added to its `ModelManager`.
+//! Checks collect every violation they find instead of stopping at the first one.
+
+mod decorators;
+mod defaults;
+mod enums;
+mod identifiers;
+mod identity;
+mod inheritance;
+mod lints;
+mod maps;
+pub(crate) mod model_file;
+mod namespaces;
+mod relationships;
+pub(crate) mod scalars;
+mod suppressions;
+pub(crate) mod validators;
+
+use serde_json::Value;
+
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::error::ValidationError;
+use crate::rules::Rule;
+use crate::semantic::model_file::{ModelFile, ModelSet};
+use crate::validator::ValidationOptions;
+
+/// Runs all semantic checks and lint rules on an AST that passed structural validation.
+/// ASTs whose root is neither a `Model` nor a `Models` have nothing to check.
+/// Types are resolved across all the models of a `Models` AST.
+/// Findings get the severity of their rule in [`ValidationOptions::rules`],
+/// unless the rule is turned off or suppressed in the model.
+pub(crate) fn validate(ast: &Value, options: &ValidationOptions) -> Vec<Diagnostic> {
+    let models = ModelSet::new(ModelFile::from_ast(ast));
+    let referenced = lints::referenced_declarations(&models);
+    let mut diagnostics = vec![];
+
+    for model in &models.models {
+        let mut findings = Findings::default();
+        identifiers::validate(model, &mut findings);
+        namespaces::validate(model, options, &mut findings);
+        validators::validate(model, &mut findings);
+        defaults::validate(model, &mut findings);
+        enums::validate(model, &models, &mut findings);
+        maps::validate(model, &models, &mut findings);
+        identity::validate(model, &models, &mut findings);
+        relationships::validate(model, &models, &mut findings);
+        inheritance::validate(model, &models, &mut findings);
+        scalars::validate(model, &models, &mut findings);
+        decorators::validate(model, &models, options, &mut findings);
+        lints::validate(model, &models, &referenced, &mut findings);
+
+        diagnostics.extend(findings.0.into_iter().filter_map(|(declaration, error)| diagnose(model, options, declaration.as_deref(), error)));
+    }
+
+    diagnostics
+}
+
+/// The violations found by the checks, with the name of the declaration each was found on.
+/// Violations about the model itself, e.g. its namespace, imports or decorators, have none.
+#[derive(Debug, Default)]
+pub(crate) struct Findings(Vec<(Option<String>, ValidationError)>);
+
+impl Findings {
+    /// Records the violations found by `check` on the declaration `name`.
+    pub(crate) fn on(&mut self, name: &str, check: impl FnOnce(&mut Vec<ValidationError>)) {
+        self.collect(Some(name), check);
+    }
+
+    /// Records the violations found by `check` on the model itself.
+    pub(crate) fn on_model(&mut self, check: impl FnOnce(&mut Vec<ValidationError>)) {
+        self.collect(None, check);
+    }
+
+    fn collect(&mut self, declaration: Option<&str>, check: impl FnOnce(&mut Vec<ValidationError>)) {
+        let mut errors = vec![];
+        check(&mut errors);
+        self.0.extend(errors.into_iter().map(|error| (declaration.map(str::to_string), error)));
+    }
+
+    #[cfg(test)]
+    pub(crate) fn into_errors(self) -> Vec<ValidationError> {
+        self.0.into_iter().map(|(_, error)| error).collect()
+    }
+}
+
+fn diagnose(model: &ModelFile, options: &ValidationOptions, declaration: Option<&str>, error: ValidationError) -> Option<Diagnostic> {
+    let Some(rule) = Rule::of(&error) else {
+        return Some(Diagnostic::error(error));
+    };
+    if options.enforce_error_rules && rule.default_severity() == Severity::Error {
+        return Some(Diagnostic::error(error));
+    }
+    if suppressions::is_suppressed(model, rule, declaration) {
+        return None;
+    }
+    let severity = options.rules.level(rule)?;
+    Some(Diagnostic::new(severity, error))
+}
+
+/// Builders for the model ASTs the checks are tested on.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use serde_json::{json, Value};
+
+    use crate::error::ValidationError;
+    use crate::semantic::model_file::{ModelFile, ModelSet};
+    use crate::semantic::Findings;
+
+    /// Runs `validate` on every model of `ast` and returns the violations it found.
+    pub(crate) fn check(ast: &Value, validate: impl Fn(&ModelFile, &ModelSet, &mut Findings)) -> Vec<ValidationError> {
+        let models = ModelSet::new(ModelFile::from_ast(ast));
+        let mut findings = Findings::default();
+        for model in &models.models {
+            validate(model, &models, &mut findings);
+        }
+        findings.into_errors()
+    }
+
+    /// A model in the `org.example@1.0.0` namespace.
+    pub(crate) fn model(declarations: Value) -> Value {
+        json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "declarations": declarations
+        })
+    }
+
+    /// A reference to the type `name`.
+    pub(crate) fn type_identifier(name: &str) -> Value {
+        json!({ "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": name })
+    }
+
+    /// A required, single-valued property of the metamodel class `class`, e.g. `StringProperty`.
+    pub(crate) fn property(class: &str, name: &str) -> Value {
+        json!({ "$class": format!("concerto.metamodel@1.0.0.{}", class), "name": name, "isArray": false, "isOptional": false })
+    }
+
+    /// A concrete concept declaration.
+    pub(crate) fn concept(name: &str, properties: Value) -> Value {
+        json!({ "$class": "concerto.metamodel@1.0.0.ConceptDeclaration", "name": name, "isAbstract": false, "properties": properties })
+    }
+}
@@ -0,0 +1,262 @@
+//! `ModelFile` is a read-only view over a `Model` AST.
+//! It plays the role of the `ModelFile` class from the JS implementation,
+//! but borrows from the JSON instead of building introspection objects.
+//! The AST is expected to have passed structural validation already,
+//! so accessors fall back to empty values instead of reporting errors.
+
+use serde_json::Value;
+
+use crate::model_manager::JsonObject;
+
+/// A single Concerto model (one namespace) inside the validated AST.
+pub(crate) struct ModelFile<'a> {
+    pub namespace: &'a str,
+    pub imports: Vec<&'a JsonObject>,
+    pub declarations: Vec<&'a JsonObject>,
+    pub decorators: Vec<&'a JsonObject>,
+}
+
+impl<'a> ModelFile<'a> {
+    /// Collects the models contained in an AST.
+    /// Both a single `Model` and a `Models` collection are accepted,
+    /// any other root yields no model.
+    pub fn from_ast(ast: &'a Value) -> Vec<ModelFile<'a>> {
+        let Some(root) = ast.as_object() else {
+            return vec![];
+        };
+        match short_class_name(root) {
+            "Model" => vec![Self::new(root)],
+            "Models" => objects(root, "models").map(Self::new).collect(),
+            _ => vec![],
+        }
+    }
+
+    fn new(model: &'a JsonObject) -> Self {
+        ModelFile {
+            namespace: str_field(model, "namespace").unwrap_or_default(),
+            imports: objects(model, "imports").collect(),
+            declarations: objects(model, "declarations").collect(),
+            decorators: objects(model, "decorators").collect(),
+        }
+    }
+
+    /// Returns the declaration with the given short name.
+    pub fn declaration(&self, name: &str) -> Option<&'a JsonObject> {
+        self.declarations
+            .iter()
+            .find(|declaration| str_field(declaration, "name") == Some(name))
+            .copied()
+    }
+}
+
+/// Outcome of resolving a type name used in a model.
+#[derive(Clone, Copy)]
+pub(crate) enum TypeResolution<'a> {
+    /// Declared in one of the models being validated.
+    Declared {
+        namespace: &'a str,
+        declaration: &'a JsonObject,
+    },
+    /// Imported from a namespace that is not part of the validated AST,
+    /// so nothing more can be said about it.
+    External,
+    /// Neither declared in the model nor imported.
+    Undeclared,
+}
+
+/// All the models of the validated AST, used to resolve types across imports.
+pub(crate) struct ModelSet<'a> {
+    pub models: Vec<ModelFile<'a>>,
+}
+
+impl<'a> ModelSet<'a> {
+    pub fn new(models: Vec<ModelFile<'a>>) -> Self {
+        ModelSet { models }
+    }
+
+    pub fn get(&self, namespace: &str) -> Option<&ModelFile<'a>> {
+        self.models.iter().find(|model| model.namespace == namespace)
+    }
+
+    /// Resolves a `TypeIdentifier` node as seen from `model`.
+    pub fn resolve_type_identifier(&self, model: &ModelFile<'a>, type_identifier: &'a JsonObject) -> TypeResolution<'a> {
+        let name = str_field(type_identifier, "name").unwrap_or_default();
+        match str_field(type_identifier, "namespace") {
+            Some(namespace) => self.resolve_in_namespace(namespace, name),
+            None => self.resolve(model, name),
+        }
+    }
+
+    /// Resolves a short type name as seen from `model`, looking at the local
+    /// declarations first and then at the imports, like the JS `ModelFile` does.
+    pub fn resolve(&self, model: &ModelFile<'a>, name: &str) -> TypeResolution<'a> {
+        if let Some(declaration) = model.declaration(name) {
+            return TypeResolution::Declared {
+                namespace: model.namespace,
+                declaration,
+            };
+        }
+
+        let imported = resolve_import(model.imports.iter().copied(), name, |namespace| {
+            matches!(self.resolve_in_namespace(namespace, name), TypeResolution::Declared { .. })
+        });
+        // A type may come from a namespace imported with `ImportAll` that is not part of the AST.
+        let imports_external = || {
+            model.imports.iter().any(|import| {
+                short_class_name(import) == "ImportAll" && self.get(str_field(import, "namespace").unwrap_or_default()).is_none()
+            })
+        };
+        match imported {
+            Some((namespace, name)) => self.resolve_in_namespace(namespace, name),
+            None if imports_external() => TypeResolution::External,
+            None => TypeResolution::Undeclared,
+        }
+    }
+
+    /// Walks a declaration and its ancestors through their `superType`.
+    /// Returns the visited declarations with their namespace, starting with `declaration`,
+    /// and whether the walk reached the root of the hierarchy. It stops early on types
+    /// that cannot be resolved and on cycles.
+    pub fn hierarchy(&self, namespace: &'a str, declaration: &'a JsonObject) -> (Vec<(&'a str, &'a JsonObject)>, bool) {
+        let mut chain = vec![(namespace, declaration)];
+        let (mut namespace, mut current) = (namespace, declaration);
+        loop {
+            let Some(super_type) = current.get("superType").and_then(Value::as_object) else {
+                return (chain, true);
+            };
+            let Some(model) = self.get(namespace) else {
+                return (chain, false);
+            };
+            match self.resolve_type_identifier(model, super_type) {
+                TypeResolution::Declared { namespace: parent_namespace, declaration: parent } => {
+                    if chain.iter().any(|(_, visited)| std::ptr::eq(*visited, parent)) {
+                        return (chain, false);
+                    }
+                    chain.push((parent_namespace, parent));
+                    (namespace, current) = (parent_namespace, parent);
+                }
+                _ => return (chain, false),
+            }
+        }
+    }
+
+    /// Returns the names of the declarations of the supertype cycle a declaration belongs to,
+    /// starting and ending with it, e.g. `[A, B, A]`, or `None` if it is not part of a cycle.
+    /// Declarations extending a cycle without being part of it are not reported.
+    pub fn supertype_cycle(&self, namespace: &'a str, declaration: &'a JsonObject) -> Option<Vec<&'a str>> {
+        let (chain, complete) = self.hierarchy(namespace, declaration);
+        if complete {
+            return None;
+        }
+        let (last_namespace, last) = chain.last()?;
+        let super_type = last.get("superType").and_then(Value::as_object)?;
+        match self.resolve_type_identifier(self.get(last_namespace)?, super_type) {
+            TypeResolution::Declared { declaration: parent, .. } if std::ptr::eq(parent, declaration) => Some(
+                chain
+                    .iter()
+                    .chain(std::iter::once(&(namespace, declaration)))
+                    .map(|(_, declaration)| str_field(declaration, "name").unwrap_or_default())
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Returns whether a declaration or one of its ancestors is `identified`,
+    /// or `None` when part of the hierarchy is not available.
+    pub fn is_identified(&self, namespace: &'a str, declaration: &'a JsonObject) -> Option<bool> {
+        let (chain, complete) = self.hierarchy(namespace, declaration);
+        if chain.iter().any(|(_, declaration)| declaration.contains_key("identified")) {
+            Some(true)
+        } else if complete {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    fn resolve_in_namespace(&self, namespace: &str, name: &str) -> TypeResolution<'a> {
+        match self.get(namespace) {
+            Some(model) => match model.declaration(name) {
+                Some(declaration) => TypeResolution::Declared {
+                    namespace: model.namespace,
+                    declaration,
+                },
+                None => TypeResolution::Undeclared,
+            },
+            None => TypeResolution::External,
+        }
+    }
+}
+
+/// Resolves a short type name through the `imports` of a model, like the JS `ModelFile` does.
+/// Returns the imported namespace and the name the type is declared with, which differs from `name` for aliases.
+/// `ImportAll` imports only match if `declares(namespace)` says the namespace declares `name`.
+pub(crate) fn resolve_import<'a>(
+    imports: impl IntoIterator<Item = &'a JsonObject>,
+    name: &'a str,
+    mut declares: impl FnMut(&'a str) -> bool,
+) -> Option<(&'a str, &'a str)> {
+    for import in imports {
+        let namespace = str_field(import, "namespace").unwrap_or_default();
+        match short_class_name(import) {
+            "ImportType" if str_field(import, "name") == Some(name) => return Some((namespace, name)),
+            "ImportTypes" => {
+                let aliased = objects(import, "aliasedTypes")
+                    .find(|alias| str_field(alias, "aliasedName") == Some(name))
+                    .and_then(|alias| str_field(alias, "name"));
+                if let Some(original_name) = aliased {
+                    return Some((namespace, original_name));
+                }
+                let imported = import
+                    .get("types")
+                    .and_then(Value::as_array)
+                    .is_some_and(|types| types.iter().any(|t| t.as_str() == Some(name)));
+                if imported {
+                    return Some((namespace, name));
+                }
+            }
+            "ImportAll" if declares(namespace) => return Some((namespace, name)),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns the unqualified name of the `$class` of an AST node,
+/// e.g. `ConceptDeclaration` for `concerto.metamodel@1.0.0.ConceptDeclaration`.
+pub(crate) fn short_class_name(node: &JsonObject) -> &str {
+    str_field(node, "$class")
+        .and_then(|class| class.rsplit('.').next())
+        .unwrap_or_default()
+}
+
+/// Returns a string field of an AST node.
+pub(crate) fn str_field<'a>(node: &'a JsonObject, key: &str) -> Option<&'a str> {
+    node.get(key).and_then(Value::as_str)
+}
+
+/// Iterates over the objects of an array field of an AST node.
+pub(crate) fn objects<'a>(node: &'a JsonObject, key: &str) -> impl Iterator<Item = &'a JsonObject> {
+    node.get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+}
+
+/// Iterates over the relationships of a declaration: its `RelationshipProperty`
+/// properties, e.g. `Car.owner`, and the `RelationshipMapValueType` value of a map,
+/// named after the map. Each is paired with the context it is reported on.
+pub(crate) fn relationships(declaration: &JsonObject) -> impl Iterator<Item = (String, &JsonObject)> {
+    let declaration_name = str_field(declaration, "name").unwrap_or_default();
+    let properties = objects(declaration, "properties")
+        .filter(|property| short_class_name(property) == "RelationshipProperty")
+        .map(move |property| (format!("{}.{}", declaration_name, str_field(property, "name").unwrap_or_default()), property));
+    let map_value = declaration
+        .get("value")
+        .and_then(Value::as_object)
+        .filter(|value| short_class_name(value) == "RelationshipMapValueType")
+        .map(|value| (declaration_name.to_string(), value));
+    properties.chain(map_value)
+}
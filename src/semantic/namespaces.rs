@@ -0,0 +1,133 @@
+//! Versioned namespaces, e.g. `org.example@1.0.0`.
+//! The version part must be a strict [semantic version](https://semver.org),
+//! which is what the JS implementation enforces in strict mode.
+
+use crate::error::ValidationError;
+use crate::semantic::model_file::{str_field, ModelFile};
+use crate::semantic::Findings;
+use crate::validator::ValidationOptions;
+
+/// A namespace split into its name and its optional version.
+pub(crate) struct Namespace<'a> {
+    pub name: &'a str,
+    pub version: Option<&'a str>,
+}
+
+impl<'a> Namespace<'a> {
+    pub fn parse(namespace: &'a str) -> Self {
+        match namespace.split_once('@') {
+            Some((name, version)) => Namespace { name, version: Some(version) },
+            None => Namespace { name: namespace, version: None },
+        }
+    }
+}
+
+pub(crate) fn validate(model: &ModelFile, options: &ValidationOptions, findings: &mut Findings) {
+    findings.on_model(|errors| {
+        let import_namespaces = model.imports.iter().filter_map(|import| str_field(import, "namespace"));
+
+        for namespace in std::iter::once(model.namespace).chain(import_namespaces) {
+            match Namespace::parse(namespace).version {
+                Some(version) => {
+                    if let Err(reason) = parse_semver(version) {
+                        errors.push(ValidationError::InvalidNamespaceVersion {
+                            namespace: namespace.to_string(),
+                            reason,
+                        });
+                    }
+                }
+                None if options.require_versioned_namespaces => {
+                    errors.push(ValidationError::UnversionedNamespace {
+                        namespace: namespace.to_string(),
+                    });
+                }
+                None => {}
+            }
+        }
+    });
+}
+
+/// Parses `MAJOR.MINOR.PATCH[-PRERELEASE][+BUILD]` following SemVer 2.0.0.
+/// Returns the numeric core, or the reason the version is rejected.
+pub(crate) fn parse_semver(version: &str) -> Result<(u64, u64, u64), String> {
+    let (version, build) = match version.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version, None),
+    };
+    let (core, pre_release) = match version.split_once('-') {
+        Some((core, pre_release)) => (core, Some(pre_release)),
+        None => (version, None),
+    };
+
+    let numbers = core.split('.').collect::<Vec<_>>();
+    if numbers.len() != 3 {
+        return Err(format!("'{}' must have the form MAJOR.MINOR.PATCH", core));
+    }
+    let numbers = numbers
+        .iter()
+        .map(|number| parse_numeric_identifier(number))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(pre_release) = pre_release {
+        for identifier in pre_release.split('.') {
+            check_alphanumeric_identifier(identifier, "pre-release")?;
+            if identifier.chars().all(|c| c.is_ascii_digit()) {
+                parse_numeric_identifier(identifier)?;
+            }
+        }
+    }
+    if let Some(build) = build {
+        for identifier in build.split('.') {
+            check_alphanumeric_identifier(identifier, "build metadata")?;
+        }
+    }
+
+    Ok((numbers[0], numbers[1], numbers[2]))
+}
+
+fn parse_numeric_identifier(number: &str) -> Result<u64, String> {
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("'{}' is not a number", number));
+    }
+    if number.len() > 1 && number.starts_with('0') {
+        return Err(format!("'{}' has a leading zero", number));
+    }
+    number.parse::<u64>().map_err(|_| format!("'{}' is too large", number))
+}
+
+fn check_alphanumeric_identifier(identifier: &str, part: &str) -> Result<(), String> {
+    if identifier.is_empty() || !identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return Err(format!("'{}' is not a valid {} identifier", identifier, part));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_namespace() {
+        let namespace = Namespace::parse("org.example@1.2.3");
+        assert_eq!(namespace.name, "org.example");
+        assert_eq!(namespace.version, Some("1.2.3"));
+        assert_eq!(Namespace::parse("org.example").version, None);
+    }
+
+    #[test]
+    fn test_valid_semver() {
+        assert_eq!(parse_semver("1.0.0"), Ok((1, 0, 0)));
+        assert_eq!(parse_semver("10.20.30"), Ok((10, 20, 30)));
+        assert!(parse_semver("1.0.0-alpha.1").is_ok());
+        assert!(parse_semver("1.0.0-0.3.7").is_ok());
+        assert!(parse_semver("1.0.0+build.5").is_ok());
+        assert!(parse_semver("1.0.0-rc-1+exp.sha.5114f85").is_ok());
+    }
+
+    #[test]
+    fn test_invalid_semver() {
+        for version in ["1.0", "latest", "1.0.0.0", "01.0.0", "1.0.x", "v1.0.0", "1.0.0-", "1.0.0-01", "1.0.0+", "1.0.0-a..b", ""] {
+            assert!(parse_semver(version).is_err(), "{} should be rejected", version);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Relationship targets.
+//! A relationship is serialized as a URI made of the target type and its
+//! identifier, so the target type (or one of its ancestors) must be identified.
+//! Enums and scalars have no identity and can never be targets.
+
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::model_manager::JsonObject;
+use crate::semantic::model_file::{relationships, short_class_name, str_field, ModelFile, ModelSet, TypeResolution};
+use crate::semantic::scalars::is_scalar;
+use crate::semantic::Findings;
+
+pub(crate) fn validate(model: &ModelFile, models: &ModelSet, findings: &mut Findings) {
+    for declaration in &model.declarations {
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            for (context, node) in relationships(declaration) {
+                check_target(model, models, node, &context, errors);
+            }
+        });
+    }
+}
+
+fn check_target(model: &ModelFile, models: &ModelSet, node: &JsonObject, context: &str, errors: &mut Vec<ValidationError>) {
+    let Some(type_identifier) = node.get("type").and_then(Value::as_object) else {
+        return;
+    };
+    let target = str_field(type_identifier, "name").unwrap_or_default();
+
+    match models.resolve_type_identifier(model, type_identifier) {
+        // Undeclared map values are reported by the map rules.
+        TypeResolution::Undeclared if short_class_name(node) == "RelationshipProperty" => {
+            errors.push(ValidationError::UndeclaredType {
+                context: context.to_string(),
+                name: target.to_string(),
+            });
+        }
+        TypeResolution::Declared { namespace, declaration } => {
+            let kind = short_class_name(declaration);
+            if kind == "EnumDeclaration" || is_scalar(kind) {
+                errors.push(ValidationError::InvalidRelationshipTarget {
+                    context: context.to_string(),
+                    target: target.to_string(),
+                    reason: format!("{} cannot be the target of a relationship", if is_scalar(kind) { "scalars" } else { "enums" }),
+                });
+            } else if models.is_identified(namespace, declaration) == Some(false) {
+                errors.push(ValidationError::UnidentifiedRelationshipTarget {
+                    context: context.to_string(),
+                    target: target.to_string(),
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, concept, model, type_identifier};
+    use serde_json::json;
+
+    fn check(ast: Value) -> Vec<ValidationError> {
+        test_support::check(&ast, validate)
+    }
+
+    fn relationship(target: &str) -> Value {
+        json!({
+            "$class": "concerto.metamodel@1.0.0.RelationshipProperty",
+            "name": "owner",
+            "type": type_identifier(target),
+            "isArray": false,
+            "isOptional": false
+        })
+    }
+
+    fn model_in(namespace: &str, imports: Value, declarations: Value) -> Value {
+        let mut model = model(declarations);
+        model["namespace"] = json!(namespace);
+        model["imports"] = imports;
+        model
+    }
+
+    fn car(target: &str) -> Value {
+        json!({
+            "$class": "concerto.metamodel@1.0.0.AssetDeclaration",
+            "name": "Car",
+            "isAbstract": false,
+            "identified": { "$class": "concerto.metamodel@1.0.0.Identified" },
+            "properties": [relationship(target)]
+        })
+    }
+
+    #[test]
+    fn test_local_targets() {
+        let ast = model(json!([car("Person"), concept("Person", json!([]))]));
+        assert!(matches!(check(ast)[..], [ValidationError::UnidentifiedRelationshipTarget { .. }]));
+
+        let ast = model(json!([car("Car")]));
+        assert!(check(ast).is_empty());
+
+        let ast = model(json!([car("Nobody")]));
+        assert!(matches!(check(ast)[..], [ValidationError::UndeclaredType { .. }]));
+    }
+
+    #[test]
+    fn test_imported_targets() {
+        let ast = json!({
+            "$class": "concerto.metamodel@1.0.0.Models",
+            "models": [
+                model_in("org.people@1.0.0", json!([]), json!([
+                    {
+                        "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration",
+                        "name": "Person",
+                        "isAbstract": false,
+                        "identified": { "$class": "concerto.metamodel@1.0.0.Identified" },
+                        "properties": []
+                    },
+                    concept("Address", json!([]))
+                ])),
+                model_in("org.cars@1.0.0", json!([
+                    {
+                        "$class": "concerto.metamodel@1.0.0.ImportTypes",
+                        "namespace": "org.people@1.0.0",
+                        "types": ["Person", "Address"]
+                    }
+                ]), json!([car("Person"), car("Address")])),
+                model_in("org.trucks@1.0.0", json!([
+                    {
+                        "$class": "concerto.metamodel@1.0.0.ImportAll",
+                        "namespace": "org.unknown@1.0.0"
+                    }
+                ]), json!([car("Anything")]))
+            ]
+        });
+        let errors = check(ast);
+        assert!(matches!(errors[..], [ValidationError::UnidentifiedRelationshipTarget { ref target, .. }] if target == "Address"));
+    }
+
+    #[test]
+    fn test_relationship_values_must_be_identified() {
+        let lookup = |value: &str| {
+            json!({
+                "$class": "concerto.metamodel@1.0.0.MapDeclaration",
+                "name": "Lookup",
+                "key": { "$class": "concerto.metamodel@1.0.0.StringMapKeyType" },
+                "value": {
+                    "$class": "concerto.metamodel@1.0.0.RelationshipMapValueType",
+                    "type": type_identifier(value)
+                }
+            })
+        };
+        let errors = check(model(json!([concept("Address", json!([])), lookup("Address")])));
+        assert!(
+            matches!(&errors[..], [ValidationError::UnidentifiedRelationshipTarget { context, target }] if context == "Lookup" && target == "Address"),
+            "{:?}",
+            errors
+        );
+
+        let errors = check(model(json!([car("Car"), lookup("Car")])));
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+
+    #[test]
+    fn test_enum_and_scalar_targets() {
+        let ast = model(json!([
+            car("Color"),
+            { "$class": "concerto.metamodel@1.0.0.EnumDeclaration", "name": "Color", "properties": [] }
+        ]));
+        let errors = check(ast);
+        assert!(matches!(&errors[..], [ValidationError::InvalidRelationshipTarget { reason, .. }] if reason.starts_with("enums")), "{:?}", errors);
+
+        let ast = model(json!([
+            car("SSN"),
+            { "$class": "concerto.metamodel@1.0.0.StringScalar", "name": "SSN" }
+        ]));
+        let errors = check(ast);
+        assert!(matches!(&errors[..], [ValidationError::InvalidRelationshipTarget { reason, .. }] if reason.starts_with("scalars")), "{:?}", errors);
+    }
+}
@@ -0,0 +1,159 @@
+//! Scalar declarations used as property types.
+//! A property typed with a scalar is an `ObjectProperty` in the AST,
+//! the scalar tells which primitive it stands for and which validators apply.
+//! The validators and defaults declared on scalars themselves are checked
+//! with the other declarations, scalar supertypes are rejected by the
+//! [`inheritance`](crate::semantic::inheritance) rules and relationships to
+//! scalars by the [`relationships`](crate::semantic::relationships) rules.
+
+use serde_json::{Number, Value};
+
+use crate::model_manager::JsonObject;
+use crate::semantic::defaults::{check_primitive_value, push_invalid_default};
+use crate::semantic::model_file::{objects, short_class_name, str_field, ModelFile, ModelSet, TypeResolution};
+use crate::semantic::Findings;
+
+pub(crate) fn is_scalar(kind: &str) -> bool {
+    kind.ends_with("Scalar")
+}
+
+/// The primitive type behind a scalar declaration, e.g. `String` for a `StringScalar`.
+pub(crate) fn scalar_primitive(scalar: &JsonObject) -> Option<&str> {
+    short_class_name(scalar).strip_suffix("Scalar")
+}
+
+/// Resolves the scalar declaration an `ObjectProperty` is typed with, if any.
+pub(crate) fn resolve_scalar<'a>(model: &ModelFile<'a>, models: &ModelSet<'a>, property: &'a JsonObject) -> Option<&'a JsonObject> {
+    if short_class_name(property) != "ObjectProperty" {
+        return None;
+    }
+    let type_identifier = property.get("type").and_then(Value::as_object)?;
+    match models.resolve_type_identifier(model, type_identifier) {
+        TypeResolution::Declared { declaration, .. } if is_scalar(short_class_name(declaration)) => Some(declaration),
+        _ => None,
+    }
+}
+
+/// Reads the default value of a property typed with a scalar as the scalar's primitive.
+/// `ObjectProperty.defaultValue` is a String in the metamodel, e.g. `"42"` for an
+/// `IntegerScalar`. Returns `None` if the string is not a value of the primitive.
+pub(crate) fn parse_default_value(primitive: &str, default_value: &str) -> Option<Value> {
+    match primitive {
+        "Integer" | "Long" => default_value.parse::<i64>().ok().map(Value::from),
+        "Double" => default_value.parse::<f64>().ok().and_then(Number::from_f64).map(Value::Number),
+        "Boolean" => default_value.parse::<bool>().ok().map(Value::Bool),
+        _ => Some(Value::String(default_value.to_string())),
+    }
+}
+
+pub(crate) fn validate(model: &ModelFile, models: &ModelSet, findings: &mut Findings) {
+    for declaration in &model.declarations {
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            for property in objects(declaration, "properties") {
+                let property_name = str_field(property, "name").unwrap_or_default();
+                let context = format!("{}.{}", declaration_name, property_name);
+
+                if let Some(scalar) = resolve_scalar(model, models, property) {
+                    if let Some(default_value) = property.get("defaultValue") {
+                        let parsed = default_value
+                            .as_str()
+                            .zip(scalar_primitive(scalar))
+                            .and_then(|(default_value, primitive)| parse_default_value(primitive, default_value));
+                        let value = parsed.as_ref().unwrap_or(default_value);
+                        if let Err(reason) = check_primitive_value(short_class_name(scalar), value, scalar) {
+                            push_invalid_default(&context, default_value, reason, errors);
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ValidationError;
+    use crate::semantic::test_support::{self, concept, model, property, type_identifier};
+    use serde_json::json;
+
+    fn ast(property: Value) -> Value {
+        model(json!([
+            {
+                "$class": "concerto.metamodel@1.0.0.StringScalar",
+                "name": "SSN",
+                "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": "^\\d{3}-\\d{2}-\\d{4}$", "flags": "" }
+            },
+            concept("Person", json!([property]))
+        ]))
+    }
+
+    fn check(ast: &Value) -> Vec<ValidationError> {
+        test_support::check(ast, validate)
+    }
+
+    fn ssn(class: &str, default_value: Option<&str>) -> Value {
+        let mut property = property(class, "ssn");
+        property["type"] = type_identifier("SSN");
+        if let Some(default_value) = default_value {
+            property["defaultValue"] = json!(default_value);
+        }
+        property
+    }
+
+    #[test]
+    fn test_resolve_scalar() {
+        let ast = ast(ssn("ObjectProperty", None));
+        let models = ModelSet::new(ModelFile::from_ast(&ast));
+        let model = &models.models[0];
+        let property = objects(model.declaration("Person").unwrap(), "properties").next().unwrap();
+        let scalar = resolve_scalar(model, &models, property).unwrap();
+        assert_eq!(scalar_primitive(scalar), Some("String"));
+        assert!(check(&ast).is_empty());
+    }
+
+    #[test]
+    fn test_scalar_property_default_value() {
+        assert!(check(&ast(ssn("ObjectProperty", Some("123-45-6789")))).is_empty());
+        let errors = check(&ast(ssn("ObjectProperty", Some("123456789"))));
+        assert!(matches!(errors[..], [ValidationError::InvalidDefaultValue { .. }]));
+    }
+
+    #[test]
+    fn test_scalar_property_primitive_default_values() {
+        let check_default = |class: &str, default_value: &str| {
+            let mut ast = ast(ssn("ObjectProperty", Some(default_value)));
+            ast["declarations"][0] = json!({ "$class": format!("concerto.metamodel@1.0.0.{}", class), "name": "SSN" });
+            check(&ast)
+        };
+        for (class, default_value) in [
+            ("IntegerScalar", "42"),
+            ("IntegerScalar", "-7"),
+            ("LongScalar", "3000000000"),
+            ("DoubleScalar", "1.5"),
+            ("DoubleScalar", "42"),
+            ("BooleanScalar", "true"),
+            ("BooleanScalar", "false"),
+        ] {
+            let errors = check_default(class, default_value);
+            assert!(errors.is_empty(), "{} {}: {:?}", class, default_value, errors);
+        }
+        for (class, default_value, reason) in [
+            ("IntegerScalar", "forty-two", "expected a 32-bit Integer"),
+            ("IntegerScalar", "3000000000", "3000000000 is outside the range of a 32-bit Integer, -2147483648 to 2147483647"),
+            ("LongScalar", "1.5", "expected a 64-bit Long"),
+            ("DoubleScalar", "NaN", "expected a Double"),
+            ("BooleanScalar", "yes", "expected a Boolean"),
+        ] {
+            let errors = check_default(class, default_value);
+            assert!(
+                matches!(&errors[..], [ValidationError::InvalidDefaultValue { reason: found, .. }] if found == reason),
+                "{} {}: {:?}",
+                class,
+                default_value,
+                errors
+            );
+        }
+    }
+}
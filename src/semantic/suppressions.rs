@@ -0,0 +1,92 @@
+//! Inline suppression of rules with the
+//! [`@Suppress`](crate::rules::SUPPRESS_DECORATOR) decorator.
+//! `@Suppress("empty-concept", "missing-description")` on a declaration silences
+//! those rules for the declaration and its properties, on the model it silences
+//! them for every declaration. Without arguments only the lint rules are suppressed:
+//! rules reporting errors by default guard the soundness of the model and must be named.
+
+use serde_json::Value;
+
+use crate::diagnostics::Severity;
+use crate::model_manager::JsonObject;
+use crate::rules::{Rule, SUPPRESS_DECORATOR};
+use crate::semantic::model_file::{objects, str_field, ModelFile};
+
+/// Returns `true` if `rule` is suppressed on the model, or on `declaration`,
+/// the name of the declaration the finding is on.
+pub(crate) fn is_suppressed(model: &ModelFile, rule: Rule, declaration: Option<&str>) -> bool {
+    if model.decorators.iter().any(|decorator| suppresses(decorator, rule)) {
+        return true;
+    }
+    declaration
+        .and_then(|name| model.declaration(name))
+        .is_some_and(|declaration| objects(declaration, "decorators").any(|decorator| suppresses(decorator, rule)))
+}
+
+fn suppresses(decorator: &JsonObject, rule: Rule) -> bool {
+    if str_field(decorator, "name") != Some(SUPPRESS_DECORATOR) {
+        return false;
+    }
+    let mut arguments = objects(decorator, "arguments").peekable();
+    if arguments.peek().is_none() {
+        return rule.default_severity() != Severity::Error;
+    }
+    arguments.any(|argument| argument.get("value").and_then(Value::as_str) == Some(rule.id()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ValidationError;
+    use serde_json::json;
+
+    fn suppress(rules: &[&str]) -> Value {
+        let arguments = rules
+            .iter()
+            .map(|rule| json!({ "$class": "concerto.metamodel@1.0.0.DecoratorString", "value": rule }))
+            .collect::<Vec<_>>();
+        json!({ "$class": "concerto.metamodel@1.0.0.Decorator", "name": "Suppress", "arguments": arguments })
+    }
+
+    fn model(model_decorators: Value, declaration_decorators: Value) -> Value {
+        json!({
+            "$class": "concerto.metamodel@1.0.0.Model",
+            "namespace": "org.example@1.0.0",
+            "decorators": model_decorators,
+            "declarations": [
+                { "$class": "concerto.metamodel@1.0.0.ConceptDeclaration", "name": "Person", "decorators": declaration_decorators }
+            ]
+        })
+    }
+
+    fn suppressed(ast: &Value, declaration: Option<&str>, error: ValidationError) -> bool {
+        let models = ModelFile::from_ast(ast);
+        is_suppressed(&models[0], Rule::of(&error).unwrap(), declaration)
+    }
+
+    #[test]
+    fn test_declaration_suppression() {
+        let ast = model(json!([]), json!([suppress(&["camel-case-property"])]));
+        let property = |declaration: &str| ValidationError::NonCamelCaseProperty { context: format!("{}.First_name", declaration) };
+        assert!(suppressed(&ast, Some("Person"), property("Person")));
+        assert!(!suppressed(&ast, Some("Address"), property("Address")));
+        assert!(!suppressed(&ast, Some("Person"), ValidationError::EmptyConcept { name: "Person".to_string() }));
+
+        let ast = model(json!([]), json!([suppress(&[])]));
+        let undeclared = || ValidationError::UndeclaredType { context: "decorator @Term on Person.name".to_string(), name: "Missing".to_string() };
+        assert!(suppressed(&ast, Some("Person"), ValidationError::EmptyConcept { name: "Person".to_string() }));
+        assert!(!suppressed(&ast, Some("Person"), undeclared()));
+        let ast = model(json!([]), json!([suppress(&["undeclared-type"])]));
+        assert!(suppressed(&ast, Some("Person"), undeclared()));
+        let unused = ValidationError::UnusedImport { namespace: "org.other@1.0.0".to_string(), name: "Address".to_string() };
+        assert!(!suppressed(&ast, None, unused));
+    }
+
+    #[test]
+    fn test_model_suppression() {
+        let ast = model(json!([suppress(&["unused-import"])]), json!([]));
+        let unused = ValidationError::UnusedImport { namespace: "org.other@1.0.0".to_string(), name: "Address".to_string() };
+        assert!(suppressed(&ast, None, unused));
+        assert!(!suppressed(&ast, Some("Person"), ValidationError::EmptyConcept { name: "Person".to_string() }));
+    }
+}
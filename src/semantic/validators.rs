@@ -0,0 +1,294 @@
+//! Consistency of the validators attached to properties and scalars.
+//! The metamodel only guarantees the JSON types of the bounds,
+//! these checks make sure the validators can actually be satisfied.
+//! The `check_*_value` helpers apply a validator to a concrete value.
+
+use std::fmt::Display;
+
+use fancy_regex::{Regex, RegexBuilder};
+use serde_json::Value;
+
+use crate::error::ValidationError;
+use crate::model_manager::JsonObject;
+use crate::numbers::as_integral;
+use crate::semantic::model_file::{objects, short_class_name, str_field, ModelFile};
+use crate::semantic::Findings;
+
+pub(crate) fn validate(model: &ModelFile, findings: &mut Findings) {
+    for declaration in &model.declarations {
+        let declaration_name = str_field(declaration, "name").unwrap_or_default();
+        findings.on(declaration_name, |errors| {
+            check_validators(declaration, declaration_name, errors);
+
+            for property in objects(declaration, "properties") {
+                let property_name = str_field(property, "name").unwrap_or_default();
+                check_validators(property, &format!("{}.{}", declaration_name, property_name), errors);
+            }
+        });
+    }
+}
+
+/// Checks the `validator` and `lengthValidator` of a property or scalar declaration.
+fn check_validators(node: &JsonObject, context: &str, errors: &mut Vec<ValidationError>) {
+    if let Some(validator) = node.get("validator").and_then(Value::as_object) {
+        match short_class_name(validator) {
+            "StringRegexValidator" => check_regex_validator(validator, context, errors),
+            "IntegerDomainValidator" => check_integer_bounds(validator, context, i32::MIN as i64, i32::MAX as i64, errors),
+            "LongDomainValidator" => check_integer_bounds(validator, context, i64::MIN, i64::MAX, errors),
+            "DoubleDomainValidator" => check_double_bounds(validator, context, errors),
+            _ => {}
+        }
+    }
+    if let Some(validator) = node.get("lengthValidator").and_then(Value::as_object) {
+        check_length_validator(validator, context, errors);
+    }
+}
+
+fn check_regex_validator(validator: &JsonObject, context: &str, errors: &mut Vec<ValidationError>) {
+    let pattern = str_field(validator, "pattern").unwrap_or_default();
+    let flags = str_field(validator, "flags").unwrap_or_default();
+    if let Err(reason) = compile_pattern(pattern, flags) {
+        errors.push(ValidationError::InvalidRegexPattern {
+            context: context.to_string(),
+            pattern: pattern.to_string(),
+            reason,
+        });
+    }
+}
+
+/// Compiles a `StringRegexValidator` pattern, translating the JS flags
+/// that change matching semantics. Flags that only matter to the JS
+/// `RegExp` object state (`g`, `y`, `d`) and Unicode modes are accepted.
+/// Patterns may use look-around and backreferences, as JS patterns do.
+pub(crate) fn compile_pattern(pattern: &str, flags: &str) -> Result<Regex, String> {
+    let mut builder = RegexBuilder::new(pattern);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'u' | 'v' | 'g' | 'y' | 'd' => &mut builder,
+            _ => return Err(format!("unsupported flag '{}'", flag)),
+        };
+    }
+    builder.build().map_err(|e| e.to_string())
+}
+
+/// Matches a compiled pattern, failing if backtracking exceeds its limit.
+pub(crate) fn matches_pattern(re: &Regex, value: &str) -> Result<bool, String> {
+    re.is_match(value).map_err(|e| format!("cannot match /{}/ against '{}': {}", re, value, e))
+}
+
+fn check_integer_bounds(validator: &JsonObject, context: &str, min: i64, max: i64, errors: &mut Vec<ValidationError>) {
+    let mut bound = |key: &str| -> Option<i64> {
+        let value = validator.get(key)?;
+        match value.as_number().and_then(as_integral).filter(|n| (min..=max).contains(n)) {
+            Some(n) => Some(n),
+            None => {
+                errors.push(ValidationError::InvalidValidatorBound {
+                    context: context.to_string(),
+                    bound: format!("{} = {}", key, value),
+                    reason: format!("must be an integer between {} and {}", min, max),
+                });
+                None
+            }
+        }
+    };
+    let lower = bound("lower");
+    let upper = bound("upper");
+    check_range(lower, upper, context, errors);
+}
+
+fn check_double_bounds(validator: &JsonObject, context: &str, errors: &mut Vec<ValidationError>) {
+    let lower = validator.get("lower").and_then(Value::as_f64);
+    let upper = validator.get("upper").and_then(Value::as_f64);
+    check_range(lower, upper, context, errors);
+}
+
+fn check_length_validator(validator: &JsonObject, context: &str, errors: &mut Vec<ValidationError>) {
+    let mut bound = |key: &str| -> Option<i64> {
+        let length = validator.get(key).and_then(Value::as_i64)?;
+        if length < 0 {
+            errors.push(ValidationError::InvalidValidatorBound {
+                context: context.to_string(),
+                bound: format!("{} = {}", key, length),
+                reason: "must not be negative".to_string(),
+            });
+            return None;
+        }
+        Some(length)
+    };
+    let min_length = bound("minLength");
+    let max_length = bound("maxLength");
+    check_range(min_length, max_length, context, errors);
+}
+
+/// Checks a string value against the `validator` and `lengthValidator`
+/// of a property or scalar declaration.
+pub(crate) fn check_string_value(value: &str, node: &JsonObject) -> Result<(), String> {
+    if let Some(validator) = node.get("validator").and_then(Value::as_object) {
+        let pattern = str_field(validator, "pattern").unwrap_or_default();
+        let flags = str_field(validator, "flags").unwrap_or_default();
+        // An invalid pattern is reported on its own by `check_regex_validator`.
+        if let Ok(re) = compile_pattern(pattern, flags) {
+            if !matches_pattern(&re, value)? {
+                return Err(format!("'{}' does not match /{}/{}", value, pattern, flags));
+            }
+        }
+    }
+    if let Some(validator) = node.get("lengthValidator").and_then(Value::as_object) {
+        let bound = |key: &str| validator.get(key).and_then(Value::as_i64);
+        check_length(value, bound("minLength"), bound("maxLength"))?;
+    }
+    Ok(())
+}
+
+/// Checks the length of a string, in characters, against the bounds of a `StringLengthValidator`.
+pub(crate) fn check_length(value: &str, min_length: Option<i64>, max_length: Option<i64>) -> Result<(), String> {
+    let length = value.chars().count() as i64;
+    if let Some(min_length) = min_length {
+        if length < min_length {
+            return Err(format!("length {} is less than minLength {}", length, min_length));
+        }
+    }
+    if let Some(max_length) = max_length {
+        if length > max_length {
+            return Err(format!("length {} is greater than maxLength {}", length, max_length));
+        }
+    }
+    Ok(())
+}
+
+/// Checks an integral value against the domain `validator` of a property or scalar declaration.
+pub(crate) fn check_integer_value(value: i64, node: &JsonObject) -> Result<(), String> {
+    let validator = node.get("validator").and_then(Value::as_object);
+    let bound = |key: &str| validator.and_then(|v| v.get(key)).and_then(Value::as_number).and_then(as_integral);
+    check_value_in_range(value, bound("lower"), bound("upper"))
+}
+
+/// Checks a floating point value against the domain `validator` of a property or scalar declaration.
+pub(crate) fn check_double_value(value: f64, node: &JsonObject) -> Result<(), String> {
+    let validator = node.get("validator").and_then(Value::as_object);
+    let bound = |key: &str| validator.and_then(|v| v.get(key)).and_then(Value::as_f64);
+    check_value_in_range(value, bound("lower"), bound("upper"))
+}
+
+pub(crate) fn check_value_in_range<T: PartialOrd + Display>(value: T, lower: Option<T>, upper: Option<T>) -> Result<(), String> {
+    if let Some(lower) = lower {
+        if value < lower {
+            return Err(format!("{} is less than lower bound {}", value, lower));
+        }
+    }
+    if let Some(upper) = upper {
+        if value > upper {
+            return Err(format!("{} is greater than upper bound {}", value, upper));
+        }
+    }
+    Ok(())
+}
+
+fn check_range<T: PartialOrd + ToString>(lower: Option<T>, upper: Option<T>, context: &str, errors: &mut Vec<ValidationError>) {
+    if let (Some(lower), Some(upper)) = (lower, upper) {
+        if lower > upper {
+            errors.push(ValidationError::InvalidValidatorRange {
+                context: context.to_string(),
+                lower: lower.to_string(),
+                upper: upper.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::semantic::test_support::{self, concept, model};
+    use serde_json::json;
+
+    fn check(property: Value) -> Vec<ValidationError> {
+        test_support::check(&model(json!([concept("Person", json!([property]))])), |model, _, findings| validate(model, findings))
+    }
+
+    #[test]
+    fn test_domain_validator_ranges() {
+        let valid = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.IntegerDomainValidator", "lower": 0, "upper": 150 }
+        });
+        assert!(check(valid).is_empty());
+
+        let inverted = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.DoubleDomainValidator", "lower": 1.5, "upper": -1.5 }
+        });
+        assert!(matches!(check(inverted)[..], [ValidationError::InvalidValidatorRange { .. }]));
+    }
+
+    #[test]
+    fn test_bounds_must_fit_their_type() {
+        let integer = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.IntegerDomainValidator", "upper": 3000000000i64 }
+        });
+        assert!(matches!(check(integer)[..], [ValidationError::InvalidValidatorBound { .. }]));
+
+        let long = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.LongDomainValidator", "lower": 0, "upper": 18446744073709551615u64 }
+        });
+        assert!(matches!(check(long)[..], [ValidationError::InvalidValidatorBound { .. }]));
+
+        let decimal = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.IntegerDomainValidator", "lower": 1e1, "upper": 150.0 }
+        });
+        assert!(check(decimal).is_empty());
+        let fraction = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.IntegerDomainValidator", "upper": 150.5 }
+        });
+        assert!(matches!(check(fraction)[..], [ValidationError::InvalidValidatorBound { .. }]));
+    }
+
+    #[test]
+    fn test_length_validator() {
+        let negative = json!({
+            "lengthValidator": { "$class": "concerto.metamodel@1.0.0.StringLengthValidator", "minLength": -1 }
+        });
+        assert!(matches!(check(negative)[..], [ValidationError::InvalidValidatorBound { .. }]));
+
+        let inverted = json!({
+            "lengthValidator": { "$class": "concerto.metamodel@1.0.0.StringLengthValidator", "minLength": 10, "maxLength": 5 }
+        });
+        assert!(matches!(check(inverted)[..], [ValidationError::InvalidValidatorRange { .. }]));
+    }
+
+    #[test]
+    fn test_regex_validator_must_compile() {
+        let valid = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": "^[A-Z]+$", "flags": "iu" }
+        });
+        assert!(check(valid).is_empty());
+
+        let unbalanced = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": "^([A-Z]+$", "flags": "" }
+        });
+        assert!(matches!(check(unbalanced)[..], [ValidationError::InvalidRegexPattern { .. }]));
+
+        let unknown_flag = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": "^a$", "flags": "x" }
+        });
+        assert!(matches!(check(unknown_flag)[..], [ValidationError::InvalidRegexPattern { .. }]));
+    }
+
+    #[test]
+    fn test_regex_look_around_and_backreferences() {
+        for pattern in [r"^(?=.*\d).{8,}$", r"^(?!admin$)[a-z]+$", r"^(?<=a)b$", r"^(\w)\1$"] {
+            let validator = json!({
+                "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": pattern, "flags": "" }
+            });
+            assert!(check(validator.clone()).is_empty(), "{}", pattern);
+        }
+
+        let node = json!({
+            "validator": { "$class": "concerto.metamodel@1.0.0.StringRegexValidator", "pattern": r"^(?=.*\d).{8,}$", "flags": "" }
+        });
+        let node = node.as_object().unwrap();
+        assert!(check_string_value("passw0rd", node).is_ok());
+        assert!(check_string_value("password", node).is_err());
+    }
+}
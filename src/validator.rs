@@ -1,24 +1,96 @@
+use serde_json::Value;
+
+use crate::diagnostics::Diagnostic;
 use crate::error::ValidationResult;
 use crate::model_manager::ModelManager;
+use crate::rules::RuleSet;
+use crate::semantic;
+
+/// Policies applied on top of the metamodel checks.
+/// Options are set on [`ValidationOptions::default`] with the builder methods,
+/// so that new ones can be added without breaking callers.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ValidationOptions {
+    /// Reject models and imports whose namespace has no `@version` part.
+    pub require_versioned_namespaces: bool,
+    /// Check decorators against the concepts that describe them,
+    /// e.g. `@Term("Person")` against a `concept Term { o String label }`.
+    pub validate_decorators: bool,
+    /// Severity overrides and disabled rules.
+    pub rules: RuleSet,
+    /// Report the findings of rules that are errors by default as errors,
+    /// ignoring `@Suppress` decorators and the overrides of `rules`.
+    pub(crate) enforce_error_rules: bool,
+}
+
+impl ValidationOptions {
+    /// See [`ValidationOptions::require_versioned_namespaces`](#structfield.require_versioned_namespaces).
+    pub fn require_versioned_namespaces(mut self, require_versioned_namespaces: bool) -> Self {
+        self.require_versioned_namespaces = require_versioned_namespaces;
+        self
+    }
+
+    /// See [`ValidationOptions::validate_decorators`](#structfield.validate_decorators).
+    pub fn validate_decorators(mut self, validate_decorators: bool) -> Self {
+        self.validate_decorators = validate_decorators;
+        self
+    }
+
+    /// Sets the severity overrides and disabled rules.
+    pub fn rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// The options of models registered for instance validation: the registry relies on the
+    /// error-level checks, which a model must not be able to switch off for itself.
+    pub(crate) fn for_instances() -> Self {
+        ValidationOptions { enforce_error_rules: true, ..Default::default() }
+    }
+}
 
 pub struct Validator {
     metamodel_manager: ModelManager,
+    options: ValidationOptions,
 }
 
 impl Validator {
     pub fn new() -> Result<Self, crate::error::ValidationError> {
+        Self::with_options(ValidationOptions::default())
+    }
+
+    pub fn with_options(options: ValidationOptions) -> Result<Self, crate::error::ValidationError> {
         let metamodel_manager = ModelManager::new()?;
 
-        Ok(Self { metamodel_manager })
+        Ok(Self { metamodel_manager, options })
     }
 
+    /// Validates the structure of the AST against the metamodel,
+    /// then runs the semantic checks and reports the first error.
     pub fn validate(&self, json_ast: &str) -> ValidationResult<()> {
-        match serde_json::from_str(json_ast) {
-            Ok(ast) => {
-                self.metamodel_manager.validate_metamodel(&ast)?;
-                Ok(())
-            }
-            Err(err) => Err(crate::ValidationError::JsonError(err)),
+        let ast = self.parse(json_ast)?;
+        match semantic::validate(&ast, &self.options).into_iter().find(Diagnostic::is_error) {
+            Some(diagnostic) => Err(diagnostic.error),
+            None => Ok(()),
         }
     }
+
+    /// Reports the findings of every semantic check and lint rule,
+    /// with the severity configured in [`ValidationOptions::rules`].
+    /// A model that is not valid JSON or fails the metamodel checks
+    /// yields that single error.
+    pub fn diagnose(&self, json_ast: &str) -> Vec<Diagnostic> {
+        match self.parse(json_ast) {
+            Ok(ast) => semantic::validate(&ast, &self.options),
+            Err(error) => vec![Diagnostic::error(error)],
+        }
+    }
+
+    /// Parses the AST and checks its structure against the metamodel.
+    fn parse(&self, json_ast: &str) -> ValidationResult<Value> {
+        let ast = serde_json::from_str(json_ast)?;
+        self.metamodel_manager.validate_metamodel(&ast)?;
+        Ok(ast)
+    }
 }
@@ -69,4 +69,228 @@ fn test_cli_version() {
     assert!(output.status.success());
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("concerto-validator 0.1.0"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_cli_require_versioned_namespaces() {
+    let content = r#"{
+        "$class": "concerto.metamodel@1.0.0.Model",
+        "namespace": "org.example",
+        "imports": [],
+        "declarations": []
+    }"#;
+    fs::write("test_unversioned_temp.json", content).expect("Failed to write test file");
+
+    let lenient = Command::new("./target/debug/concerto-validator")
+        .args(["validate", "--input", "test_unversioned_temp.json"])
+        .output()
+        .expect("Failed to execute command");
+
+    let strict = Command::new("./target/debug/concerto-validator")
+        .args(["validate", "--input", "test_unversioned_temp.json", "--require-versioned-namespaces"])
+        .output()
+        .expect("Failed to execute command");
+
+    // Clean up
+    fs::remove_file("test_unversioned_temp.json").ok();
+
+    assert!(lenient.status.success());
+    assert!(!strict.status.success());
+    let stdout = String::from_utf8_lossy(&strict.stdout);
+    assert!(stdout.contains("Namespace 'org.example' must be versioned"));
+}
+
+#[test]
+fn test_cli_lint() {
+    let content = r#"{
+        "$class": "concerto.metamodel@1.0.0.Model",
+        "namespace": "org.example@1.0.0",
+        "imports": [],
+        "declarations": [
+            {
+                "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                "name": "Empty",
+                "isAbstract": false,
+                "properties": []
+            }
+        ]
+    }"#;
+    fs::write("test_lint_temp.json", content).expect("Failed to write test file");
+
+    let output = Command::new("./target/debug/concerto-validator")
+        .args(["validate", "--input", "test_lint_temp.json", "--lint"])
+        .output()
+        .expect("Failed to execute command");
+
+    // Clean up
+    fs::remove_file("test_lint_temp.json").ok();
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("warning[CV0042]: Declaration Empty has no properties [empty-concept]"));
+}
+
+#[test]
+fn test_cli_rules() {
+    let content = r#"{
+        "$class": "concerto.metamodel@1.0.0.Model",
+        "namespace": "org.example@1.0.0",
+        "imports": [],
+        "declarations": [
+            {
+                "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                "name": "Empty",
+                "isAbstract": false,
+                "properties": []
+            }
+        ]
+    }"#;
+    fs::write("test_rules_temp.json", content).expect("Failed to write test file");
+    fs::write("test_rules_temp.toml", "[rules]\nempty-concept = \"error\"\n").expect("Failed to write test file");
+
+    let output = Command::new("./target/debug/concerto-validator")
+        .args(["validate", "--input", "test_rules_temp.json", "--rules", "test_rules_temp.toml"])
+        .output()
+        .expect("Failed to execute command");
+
+    // Clean up
+    fs::remove_file("test_rules_temp.json").ok();
+    fs::remove_file("test_rules_temp.toml").ok();
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Declaration Empty has no properties"));
+}
+
+#[test]
+fn test_cli_explain() {
+    let output = Command::new("./target/debug/concerto-validator")
+        .args(["explain", "CV0030"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("# CV0030: Undeclared type"));
+
+    let unknown = Command::new("./target/debug/concerto-validator")
+        .args(["explain", "CV9999"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!unknown.status.success());
+}
+
+#[test]
+fn test_cli_validate_instance() {
+    let model = r#"{
+        "$class": "concerto.metamodel@1.0.0.Model",
+        "namespace": "org.example@1.0.0",
+        "imports": [],
+        "declarations": [
+            {
+                "$class": "concerto.metamodel@1.0.0.ConceptDeclaration",
+                "name": "Person",
+                "isAbstract": false,
+                "properties": [
+                    {
+                        "$class": "concerto.metamodel@1.0.0.StringProperty",
+                        "name": "name",
+                        "isArray": false,
+                        "isOptional": false
+                    }
+                ]
+            }
+        ]
+    }"#;
+    fs::write("test_instance_model_temp.json", model).expect("Failed to write test file");
+    fs::write("test_instance_valid_temp.json", r#"{ "$class": "org.example@1.0.0.Person", "name": "Ada" }"#).expect("Failed to write test file");
+    fs::write("test_instance_invalid_temp.json", r#"{ "$class": "org.example@1.0.0.Person" }"#).expect("Failed to write test file");
+
+    let valid = Command::new("./target/debug/concerto-validator")
+        .args(["validate-instance", "--model", "test_instance_model_temp.json", "--input", "test_instance_valid_temp.json"])
+        .output()
+        .expect("Failed to execute command");
+
+    let invalid = Command::new("./target/debug/concerto-validator")
+        .args(["validate-instance", "--model", "test_instance_model_temp.json", "--input", "test_instance_invalid_temp.json"])
+        .output()
+        .expect("Failed to execute command");
+
+    // Clean up
+    fs::remove_file("test_instance_model_temp.json").ok();
+    fs::remove_file("test_instance_valid_temp.json").ok();
+    fs::remove_file("test_instance_invalid_temp.json").ok();
+
+    assert!(valid.status.success());
+    assert!(!invalid.status.success());
+    let stdout = String::from_utf8_lossy(&invalid.stdout);
+    assert!(stdout.contains("Missing required property: name"));
+}
+
+#[test]
+fn test_cli_validate_dataset() {
+    let model = r#"{
+        "$class": "concerto.metamodel@1.0.0.Model",
+        "namespace": "org.example@1.0.0",
+        "imports": [],
+        "declarations": [
+            {
+                "$class": "concerto.metamodel@1.0.0.ParticipantDeclaration",
+                "name": "Person",
+                "isAbstract": false,
+                "identified": { "$class": "concerto.metamodel@1.0.0.IdentifiedBy", "name": "email" },
+                "properties": [
+                    {
+                        "$class": "concerto.metamodel@1.0.0.StringProperty",
+                        "name": "email",
+                        "isArray": false,
+                        "isOptional": false
+                    },
+                    {
+                        "$class": "concerto.metamodel@1.0.0.RelationshipProperty",
+                        "name": "manager",
+                        "type": { "$class": "concerto.metamodel@1.0.0.TypeIdentifier", "name": "Person" },
+                        "isArray": false,
+                        "isOptional": true
+                    }
+                ]
+            }
+        ]
+    }"#;
+    let dataset = r#"{ "$class": "org.example@1.0.0.Person", "email": "ada" }
+{ "$class": "org.example@1.0.0.Person", "email": "grace", "manager": "ada" }
+{ "$class": "org.example@1.0.0.Person", "email": "ada", "manager": "alan" }
+"#;
+    fs::write("test_dataset_model_temp.json", model).expect("Failed to write test file");
+    fs::write("test_dataset_temp.ndjson", dataset).expect("Failed to write test file");
+
+    let run = |args: &[&str]| {
+        Command::new("./target/debug/concerto-validator")
+            .args(["validate-dataset", "--model", "test_dataset_model_temp.json"])
+            .args(args)
+            .output()
+            .expect("Failed to execute command")
+    };
+    let invalid = run(&["--input", "test_dataset_temp.ndjson"]);
+    let allowed = run(&["--input", "test_dataset_temp.ndjson", "--allow", "org.example@1.0.0.Person#alan"]);
+    let missing = run(&["--input", "test_dataset_missing_temp.ndjson", "--input", "test_dataset_temp.ndjson", "--fail-early", "--require-utc"]);
+
+    // Clean up
+    fs::remove_file("test_dataset_model_temp.json").ok();
+    fs::remove_file("test_dataset_temp.ndjson").ok();
+
+    assert!(!invalid.status.success());
+    let stdout = String::from_utf8_lossy(&invalid.stdout);
+    assert!(stdout.contains("line 3: [CV0052] Duplicate identifier ada of org.example@1.0.0.Person, first used on line 1"), "{}", stdout);
+    assert!(stdout.contains("line 3: [CV0053] Dangling reference org.example@1.0.0.Person#alan in manager"), "{}", stdout);
+
+    let stdout = String::from_utf8_lossy(&allowed.stdout);
+    assert!(!stdout.contains("CV0053"), "{}", stdout);
+    assert!(stdout.contains("CV0052"), "{}", stdout);
+
+    assert!(!missing.status.success());
+    let stdout = String::from_utf8_lossy(&missing.stdout);
+    assert!(stdout.contains("Stopping validation due to --fail-early flag."), "{}", stdout);
+    assert!(!stdout.contains("CV0052"), "{}", stdout);
+}